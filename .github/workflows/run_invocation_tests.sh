@@ -4,5 +4,6 @@
 # shellcheck source=/dev/null
 source test_profile
 
-# Run all tests with invocation feature (enables JavaVM ITs)
-cargo test --features=invocation
+# Run all tests with invocation feature (enables JavaVM ITs), along with the
+# tests of the optional macros and serde features
+cargo test --workspace --features=invocation,macros,serde
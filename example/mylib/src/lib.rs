@@ -5,7 +5,7 @@ use jni::JNIEnv;
 // These objects are what you should use as arguments to your native function.
 // They carry extra lifetime information to prevent them escaping this context
 // and getting used after being GC'd.
-use jni::objects::{GlobalRef, JClass, JObject, JString};
+use jni::objects::{GlobalRef, JByteArray, JClass, JObject, JString};
 
 // This is just a pointer. We'll be returning it from our function.
 // We can't return one of the objects with lifetime information because the
@@ -45,7 +45,7 @@ pub extern "system" fn Java_HelloWorld_hello(
 pub extern "system" fn Java_HelloWorld_helloByte(
     env: JNIEnv,
     _class: JClass,
-    input: jbyteArray,
+    input: JByteArray,
 ) -> jbyteArray {
     // First, we have to get the byte[] out of java.
     let _input = env.convert_byte_array(input).unwrap();
@@ -54,7 +54,7 @@ pub extern "system" fn Java_HelloWorld_helloByte(
     let buf = [1; 2000];
     let output = env.byte_array_from_slice(&buf).unwrap();
     // Finally, extract the raw pointer to return.
-    output
+    output.into_inner()
 }
 
 #[no_mangle]
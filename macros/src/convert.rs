@@ -0,0 +1,325 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{ext::IdentExt, spanned::Spanned, Attribute, Data, DeriveInput, Fields, LitStr, Type};
+
+use crate::signature;
+
+/// Which of the two traits is derived.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Direction {
+    FromJava,
+    IntoJava,
+}
+
+struct ContainerArgs {
+    /// The internal name of the class, e.g. `java/awt/Point`.
+    class: String,
+    record: bool,
+}
+
+#[derive(Default)]
+struct MemberArgs {
+    rename: Option<LitStr>,
+    signature: Option<LitStr>,
+}
+
+fn parse_container_args(input: &DeriveInput) -> syn::Result<ContainerArgs> {
+    let mut class = None;
+    let mut record = false;
+
+    for attr in jni_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("class") {
+                if class.is_some() {
+                    return Err(meta.error("duplicate jni argument"));
+                }
+                class = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("record") {
+                record = true;
+            } else {
+                return Err(meta.error("unsupported jni argument"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let class = class.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing `#[jni(class = \"...\")]` attribute, e.g. `#[jni(class = \"java.awt.Point\")]`",
+        )
+    })?;
+    let name = class.value().replace('.', "/");
+    if name.is_empty() || name.contains(';') || name.starts_with('[') {
+        return Err(syn::Error::new(class.span(), "invalid class name"));
+    }
+
+    Ok(ContainerArgs {
+        class: name,
+        record,
+    })
+}
+
+fn parse_member_args(attrs: &[Attribute], allow_signature: bool) -> syn::Result<MemberArgs> {
+    let mut args = MemberArgs::default();
+
+    for attr in jni_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("rename") {
+                &mut args.rename
+            } else if allow_signature && meta.path.is_ident("signature") {
+                &mut args.signature
+            } else {
+                return Err(meta.error("unsupported jni argument"));
+            };
+            if slot.is_some() {
+                return Err(meta.error("duplicate jni argument"));
+            }
+            *slot = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        })?;
+    }
+
+    if let Some(sig) = &args.signature {
+        signature::parse_field(&sig.value()).map_err(|e| syn::Error::new(sig.span(), e))?;
+    }
+
+    Ok(args)
+}
+
+fn jni_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("jni"))
+}
+
+pub fn expand(input: TokenStream, direction: Direction) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "types converted from or into Java objects can't be generic",
+        ));
+    }
+    let args = parse_container_args(&input)?;
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let mut members = Vec::new();
+                for field in &fields.named {
+                    let field_args = parse_member_args(&field.attrs, true)?;
+                    let ident = field.ident.as_ref().unwrap();
+                    let java_name = match &field_args.rename {
+                        Some(name) => name.value(),
+                        None => ident.unraw().to_string(),
+                    };
+                    members.push(Member {
+                        ident: quote!(#ident),
+                        java_name,
+                        ty: &field.ty,
+                        signature: field_args.signature,
+                    });
+                }
+                expand_struct(&args, &members, direction)
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "only structs with named fields can be converted from or into Java objects",
+                ))
+            }
+        },
+        Data::Enum(data) => {
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "only fieldless enums can be converted from or into Java enums",
+                    ));
+                }
+                let variant_args = parse_member_args(&variant.attrs, false)?;
+                let java_name = match &variant_args.rename {
+                    Some(name) => name.value(),
+                    None => variant.ident.unraw().to_string(),
+                };
+                variants.push((&variant.ident, java_name));
+            }
+            if args.record {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`record` can't be used for enums",
+                ));
+            }
+            expand_enum(&args, &variants, direction)
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "unions can't be converted from or into Java objects",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let class_sig = format!("L{};", args.class);
+    Ok(match direction {
+        Direction::FromJava => quote! {
+            impl<'a> ::jni::convert::FromJava<'a> for #ident {
+                fn from_java(
+                    env: &::jni::JNIEnv<'a>,
+                    obj: ::jni::objects::JObject<'a>,
+                ) -> ::jni::errors::Result<Self> {
+                    #body
+                }
+            }
+
+            impl<'a> ::jni::convert::FromJavaValue<'a> for #ident {
+                const SIGNATURE: &'static str = #class_sig;
+
+                fn from_jvalue(
+                    env: &::jni::JNIEnv<'a>,
+                    value: ::jni::objects::JValue<'a>,
+                ) -> ::jni::errors::Result<Self> {
+                    <Self as ::jni::convert::FromJava<'a>>::from_java(env, value.l()?)
+                }
+            }
+        },
+        Direction::IntoJava => quote! {
+            impl<'a> ::jni::convert::IntoJava<'a> for #ident {
+                fn into_java(
+                    self,
+                    env: &::jni::JNIEnv<'a>,
+                ) -> ::jni::errors::Result<::jni::objects::JObject<'a>> {
+                    #body
+                }
+            }
+
+            impl<'a> ::jni::convert::IntoJavaValue<'a> for #ident {
+                const SIGNATURE: &'static str = #class_sig;
+
+                fn into_jvalue(
+                    self,
+                    env: &::jni::JNIEnv<'a>,
+                ) -> ::jni::errors::Result<::jni::objects::JValue<'a>> {
+                    ::core::result::Result::Ok(::jni::objects::JValue::Object(
+                        <Self as ::jni::convert::IntoJava<'a>>::into_java(self, env)?,
+                    ))
+                }
+            }
+        },
+    })
+}
+
+struct Member<'i> {
+    ident: TokenStream,
+    java_name: String,
+    ty: &'i Type,
+    signature: Option<LitStr>,
+}
+
+impl Member<'_> {
+    fn signature(&self, direction: Direction) -> TokenStream {
+        let ty = self.ty;
+        match (&self.signature, direction) {
+            (Some(sig), _) => quote!(#sig),
+            (None, Direction::FromJava) => {
+                quote!(<#ty as ::jni::convert::FromJavaValue<'a>>::SIGNATURE)
+            }
+            (None, Direction::IntoJava) => {
+                quote!(<#ty as ::jni::convert::IntoJavaValue<'a>>::SIGNATURE)
+            }
+        }
+    }
+}
+
+fn expand_struct(args: &ContainerArgs, members: &[Member], direction: Direction) -> TokenStream {
+    let class = &args.class;
+    let binary_name = class.replace('/', ".");
+    let support = quote!(::jni::convert::derive_support);
+
+    match direction {
+        Direction::FromJava => {
+            let values = members.iter().map(|member| {
+                let ident = &member.ident;
+                let name = &member.java_name;
+                let sig = member.signature(direction);
+                if args.record {
+                    quote!(#ident: #support::call_accessor(env, obj, #name, #sig)?)
+                } else {
+                    quote!(#ident: #support::get_field(env, obj, #name, #sig)?)
+                }
+            });
+            quote! {
+                #support::check_instance(env, obj, #class, #binary_name)?;
+                ::core::result::Result::Ok(Self { #(#values),* })
+            }
+        }
+        Direction::IntoJava if args.record => {
+            let sigs = members.iter().map(|member| member.signature(direction));
+            let values = members.iter().map(|member| {
+                let ident = &member.ident;
+                quote!(::jni::convert::IntoJavaValue::into_jvalue(self.#ident, env)?)
+            });
+            quote! {
+                #support::new_record(env, #class, &[#(#sigs),*], ::std::vec![#(#values),*])
+            }
+        }
+        Direction::IntoJava => {
+            let sets = members.iter().map(|member| {
+                let ident = &member.ident;
+                let name = &member.java_name;
+                let sig = member.signature(direction);
+                quote!(#support::set_field(env, obj.as_obj(), #name, #sig, self.#ident)?;)
+            });
+            // The object is deleted if setting a field fails.
+            quote! {
+                let obj = env.auto_local(env.new_object(#class, "()V", &[])?);
+                #(#sets)*
+                ::core::result::Result::Ok(obj.forget())
+            }
+        }
+    }
+}
+
+fn expand_enum(
+    args: &ContainerArgs,
+    variants: &[(&syn::Ident, String)],
+    direction: Direction,
+) -> TokenStream {
+    let class = &args.class;
+    let binary_name = class.replace('/', ".");
+    let class_sig = format!("L{};", class);
+    let support = quote!(::jni::convert::derive_support);
+
+    match direction {
+        Direction::FromJava => {
+            let arms = variants
+                .iter()
+                .map(|(ident, name)| quote!(#name => ::core::result::Result::Ok(Self::#ident),));
+            quote! {
+                #support::check_instance(env, obj, #class, #binary_name)?;
+                let name = #support::enum_name(env, obj)?;
+                match name.as_str() {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(::jni::errors::Error::Conversion(
+                        ::std::format!(
+                            "no variant of {} for the constant {}",
+                            #binary_name, name
+                        ),
+                    )),
+                }
+            }
+        }
+        Direction::IntoJava => {
+            let arms = variants
+                .iter()
+                .map(|(ident, name)| quote!(Self::#ident => #name,));
+            quote! {
+                let name = match self {
+                    #(#arms)*
+                };
+                #support::enum_constant(env, #class, name, #class_sig)
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse::Parser, spanned::Spanned, FnArg, GenericParam, ItemFn, LitStr, ReturnType,
+};
+
+use crate::{mangle, signature};
+
+struct ExportArgs {
+    class: LitStr,
+    name: Option<LitStr>,
+    signature: Option<LitStr>,
+}
+
+fn parse_args(attr: TokenStream) -> syn::Result<ExportArgs> {
+    let mut class = None;
+    let mut name = None;
+    let mut signature = None;
+
+    let parser = syn::meta::parser(|meta| {
+        let slot = if meta.path.is_ident("class") {
+            &mut class
+        } else if meta.path.is_ident("name") {
+            &mut name
+        } else if meta.path.is_ident("signature") {
+            &mut signature
+        } else {
+            return Err(meta.error("unsupported jni_export argument"));
+        };
+        if slot.is_some() {
+            return Err(meta.error("duplicate jni_export argument"));
+        }
+        *slot = Some(meta.value()?.parse::<LitStr>()?);
+        Ok(())
+    });
+    parser.parse2(attr)?;
+
+    let class = class.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing `class = \"...\"` argument, e.g. `#[jni_export(class = \"com.example.Foo\")]`",
+        )
+    })?;
+
+    Ok(ExportArgs {
+        class,
+        name,
+        signature,
+    })
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let args = parse_args(attr)?;
+    let func: ItemFn = syn::parse2(item)?;
+    let sig = &func.sig;
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "native methods can't be `async`",
+        ));
+    }
+    if let Some(abi) = &sig.abi {
+        return Err(syn::Error::new(
+            abi.span(),
+            "the `extern \"system\"` function is generated by `jni_export`, remove the ABI",
+        ));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(syn::Error::new(
+            variadic.span(),
+            "native methods can't be variadic",
+        ));
+    }
+    for param in &sig.generics.params {
+        if !matches!(param, GenericParam::Lifetime(_)) {
+            return Err(syn::Error::new(
+                param.span(),
+                "native methods can only be generic over lifetimes",
+            ));
+        }
+    }
+
+    let mut arg_types = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Typed(pat_type) => arg_types.push(&*pat_type.ty),
+            FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "`jni_export` can only be used on free functions",
+                ))
+            }
+        }
+    }
+    if arg_types.len() < 2 {
+        return Err(syn::Error::new(
+            sig.inputs.span(),
+            "native methods take a `JNIEnv` followed by a `JClass` (for static methods) or \
+             `JObject` (for instance methods), followed by the method arguments",
+        ));
+    }
+    let env_type = arg_types.remove(0);
+
+    let java_name = match &args.name {
+        Some(name) => name.value(),
+        None => sig.ident.unraw().to_string(),
+    };
+
+    let overload_args = match &args.signature {
+        Some(signature) => {
+            let value = signature.value();
+            let parsed = signature::parse_method(&value)
+                .map_err(|e| syn::Error::new(signature.span(), e))?;
+            let count = parsed.args.len();
+            // The `JClass`/`JObject` argument isn't part of the signature.
+            if count != arg_types.len() - 1 {
+                return Err(syn::Error::new(
+                    signature.span(),
+                    format!(
+                        "signature has {} argument(s) but the function takes {}",
+                        count,
+                        arg_types.len() - 1
+                    ),
+                ));
+            }
+            Some(parsed.args_str.to_owned())
+        }
+        None => None,
+    };
+
+    let symbol = format_ident!(
+        "{}",
+        mangle::symbol_name(&args.class.value(), &java_name, overload_args.as_deref()),
+        span = sig.ident.span()
+    );
+
+    let fn_ident = &sig.ident;
+    let generics = &sig.generics;
+    let where_clause = &sig.generics.where_clause;
+    let ret_type = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, ty) => quote!(#ty),
+    };
+    let arg_idents: Vec<_> = (0..arg_types.len())
+        .map(|i| format_ident!("__jni_arg{}", i))
+        .collect();
+
+    let call = quote!(#fn_ident(__jni_env, #(#arg_idents),*));
+    let call = match &sig.unsafety {
+        Some(_) => quote!(unsafe { #call }),
+        None => call,
+    };
+
+    let wrapper = quote! {
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub extern "system" fn #symbol #generics (
+            __jni_env: #env_type,
+            #( #arg_idents: <#arg_types as ::jni::native::NativeArg>::Abi ),*
+        ) -> <#ret_type as ::jni::native::NativeReturn>::Abi #where_clause {
+            #(
+                let #arg_idents = unsafe {
+                    <#arg_types as ::jni::native::NativeArg>::from_abi(#arg_idents)
+                };
+            )*
+            ::jni::JNIEnv::catch_and_throw(&__jni_env, move || #call)
+        }
+    };
+
+    Ok(quote! {
+        #func
+
+        #wrapper
+    })
+}
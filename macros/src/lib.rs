@@ -0,0 +1,164 @@
+//! Procedural macros for the [`jni`](https://docs.rs/jni) crate.
+//!
+//! These are re-exported by `jni` when its `macros` feature is enabled, and
+//! should be used through it rather than by depending on this crate directly.
+
+#![warn(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+mod convert;
+mod export;
+mod mangle;
+mod sig;
+mod signature;
+
+/// Exports a Rust function as the implementation of a Java `native` method.
+///
+/// This keeps the annotated function as is, and generates an
+/// `extern "system"` function next to it with the symbol name the JVM looks
+/// up, following the name mangling rules of the JNI specification (`_1`,
+/// `_2`, `_3` and `_0xxxx` escapes, and the `__` suffix for overloads).
+///
+/// The annotated function must be a free function whose first argument is a
+/// `JNIEnv`, followed by the `JClass` (static methods) or `JObject`
+/// (instance methods) the method was called on, followed by the arguments
+/// of the Java method. Arguments and the return value are converted from and
+/// to their raw JNI types with the `jni::native::NativeArg` and
+/// `jni::native::NativeReturn` traits, so e.g. a `java.lang.String` argument
+/// can be declared as `JString` and a `boolean` as `bool`.
+///
+/// The function may also return a `Result` whose error type implements
+/// `jni::errors::ToException`, such as `jni::errors::Error`: an `Err` is
+/// thrown as a Java exception from the native method. A panic in the function
+/// is caught before it unwinds into the JVM, and turned into a
+/// `java.lang.RuntimeException`. See `JNIEnv::catch_and_throw`.
+///
+/// # Arguments
+///
+/// * `class` (required): the fully-qualified name of the class declaring the
+///   native method, e.g. `"com.example.Foo"`, `"com/example/Foo"` or
+///   `"com.example.Foo$Inner"` for a nested class.
+/// * `name`: the name of the Java method. Defaults to the name of the Rust
+///   function.
+/// * `signature`: the JNI signature of the method, e.g. `"(I)V"`. Only needed
+///   when the native method is overloaded, to generate the long symbol name.
+///   It is checked at compile time.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jni::{jni_export, objects::{JClass, JString}, JNIEnv};
+///
+/// // Implements `static native String hello(String input)` of `com.example.HelloWorld`.
+/// #[jni_export(class = "com.example.HelloWorld")]
+/// fn hello<'a>(env: JNIEnv<'a>, _class: JClass<'a>, input: JString<'a>) -> JString<'a> {
+///     let input: String = env.get_string(input).unwrap().into();
+///     env.new_string(format!("Hello, {}!", input)).unwrap()
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn jni_export(attr: TokenStream, item: TokenStream) -> TokenStream {
+    export::expand(attr.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Checks a JNI method signature at compile time, and creates a typed
+/// `jni::signature::MethodSignature` for it.
+///
+/// The `MethodSignature` has the Rust types of the arguments and return
+/// value of the method, so that calls made with it (e.g. with
+/// `JNIEnv::call_method_typed`) take a tuple of Rust arguments and return the
+/// result with its Rust type. It can be used in constant expressions.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jni::{jni_sig, objects::JString, signature::MethodSignature, sys::jint};
+///
+/// const INDEX_OF: MethodSignature<(JString<'static>, jint), jint> =
+///     jni_sig!("(Ljava/lang/String;I)I");
+/// ```
+#[proc_macro]
+pub fn jni_sig(input: TokenStream) -> TokenStream {
+    sig::expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `jni::convert::FromJava` (and `jni::convert::FromJavaValue`) for a
+/// struct with named fields, or a fieldless enum, to convert a Java object of
+/// the class given with `#[jni(class = "...")]` into it.
+///
+/// For a struct, each field is read from the Java field of the same name, or
+/// with `#[jni(record)]` on the struct, from the accessor method of the same
+/// name, e.g. `x()` for the `x` component of a record. The value is converted
+/// with `FromJavaValue`, which maps primitive fields to the `jni::sys` types
+/// (or `bool`), and object fields to `String`, `Option`, `Vec`, `HashMap`,
+/// `GlobalRef` or other types deriving `FromJava`.
+///
+/// For an enum, the Java enum constant is mapped to the variant of the same
+/// name. A constant without a variant is reported with `Error::Conversion`.
+///
+/// # Attributes
+///
+/// * On the type, `class` (required): the fully-qualified name of the class,
+///   e.g. `"java.awt.Point"` or `"java/awt/Point"`, and `record` to use
+///   accessor methods instead of fields.
+/// * On a field or variant, `rename`: the name of the Java field, accessor
+///   method or enum constant, if it isn't the Rust name.
+/// * On a field, `signature`: the JNI type signature of the Java field, e.g.
+///   `"Ljava/util/ArrayList;"`, if it isn't the one of the Rust type (e.g.
+///   `Ljava/util/List;` for a `Vec`). It is checked at compile time.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jni::convert::{FromJava, IntoJava};
+///
+/// #[derive(FromJava, IntoJava)]
+/// #[jni(class = "java.awt.Point")]
+/// struct Point {
+///     x: jint,
+///     #[jni(rename = "y")]
+///     vertical: jint,
+/// }
+///
+/// #[derive(FromJava, IntoJava)]
+/// #[jni(class = "java.time.DayOfWeek")]
+/// enum Day {
+///     #[jni(rename = "MONDAY")]
+///     Monday,
+///     // ...
+/// }
+///
+/// let point = Point::from_java(&env, obj)?;
+/// ```
+#[proc_macro_derive(FromJava, attributes(jni))]
+pub fn derive_from_java(input: TokenStream) -> TokenStream {
+    convert::expand(input.into(), convert::Direction::FromJava)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `jni::convert::IntoJava` (and `jni::convert::IntoJavaValue`) for a
+/// struct with named fields, or a fieldless enum, to convert it into a Java
+/// object of the class given with `#[jni(class = "...")]`. This is the
+/// reverse of `FromJava`, and takes the same attributes.
+///
+/// For a struct, the object is created with the constructor without
+/// arguments, and each field is then set to the converted value of the Rust
+/// field. With `#[jni(record)]`, the object is created with the canonical
+/// constructor of the record instead, which takes the fields in order.
+///
+/// For an enum, the variant is mapped to the Java enum constant of the same
+/// name.
+#[proc_macro_derive(IntoJava, attributes(jni))]
+pub fn derive_into_java(input: TokenStream) -> TokenStream {
+    convert::expand(input.into(), convert::Direction::IntoJava)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
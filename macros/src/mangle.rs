@@ -0,0 +1,86 @@
+use std::fmt::Write;
+
+/// Builds the symbol name the JVM looks up for a native method.
+///
+/// `class` is a fully-qualified class name, either in binary form
+/// (`com.example.Foo$Inner`) or internal form (`com/example/Foo$Inner`).
+/// When `args` (the argument part of a method signature, without the
+/// parentheses) is given, the "long" name used to tell overloads apart is
+/// built.
+pub fn symbol_name(class: &str, method: &str, args: Option<&str>) -> String {
+    let mut symbol = format!(
+        "Java_{}_{}",
+        mangle(&class.replace('.', "/")),
+        mangle(method)
+    );
+    if let Some(args) = args {
+        symbol.push_str("__");
+        symbol.push_str(&mangle(args));
+    }
+    symbol
+}
+
+/// Escapes a name as described in the "Resolving Native Method Names"
+/// section of the JNI specification.
+pub fn mangle(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '/' => out.push('_'),
+            '_' => out.push_str("_1"),
+            ';' => out.push_str("_2"),
+            '[' => out.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    write!(out, "_0{:04x}", unit).unwrap();
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_symbol_name() {
+        assert_eq!(
+            symbol_name("HelloWorld", "hello", None),
+            "Java_HelloWorld_hello"
+        );
+        assert_eq!(
+            symbol_name("com.example.Foo", "bar", None),
+            "Java_com_example_Foo_bar"
+        );
+        assert_eq!(
+            symbol_name("com/example/Foo", "bar", None),
+            "Java_com_example_Foo_bar"
+        );
+    }
+
+    #[test]
+    fn test_symbol_name_escapes() {
+        assert_eq!(
+            symbol_name("my_pkg.Foo$Bar", "do_it", None),
+            "Java_my_1pkg_Foo_00024Bar_do_1it"
+        );
+        assert_eq!(symbol_name("Foo", "caf\u{e9}", None), "Java_Foo_caf_000e9");
+        assert_eq!(
+            symbol_name("Foo", "\u{1F600}", None),
+            "Java_Foo__0d83d_0de00"
+        );
+    }
+
+    #[test]
+    fn test_symbol_name_overloaded() {
+        assert_eq!(
+            symbol_name("Foo", "bar", Some("I[Ljava/lang/String;")),
+            "Java_Foo_bar__I_3Ljava_lang_String_2"
+        );
+        assert_eq!(symbol_name("Foo", "bar", Some("")), "Java_Foo_bar__");
+    }
+}
@@ -0,0 +1,49 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+use crate::signature::{self, JavaType};
+
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let sig: LitStr = syn::parse2(input)?;
+    let value = sig.value();
+    let parsed = signature::parse_method(&value).map_err(|e| syn::Error::new(sig.span(), e))?;
+
+    let args = parsed.args.iter().map(rust_type);
+    let ret = match &parsed.ret {
+        Some(ty) => rust_type(ty),
+        None => quote!(()),
+    };
+
+    // The signature was parsed above and the types are derived from it, so
+    // they match. The `let` is only there to allow the `unsafe` block, which
+    // is unused when the macro is called in an `unsafe` block itself.
+    Ok(quote! {
+        {
+            #[allow(unused_unsafe)]
+            let sig = unsafe {
+                ::jni::signature::MethodSignature::<(#(#args,)*), #ret>::new_unchecked(#sig)
+            };
+            sig
+        }
+    })
+}
+
+// The Rust type used for a Java type in `jni::signature::MethodSignature`.
+fn rust_type(ty: &JavaType) -> TokenStream {
+    match ty {
+        JavaType::Primitive('Z') => quote!(bool),
+        JavaType::Primitive('B') => quote!(::jni::sys::jbyte),
+        JavaType::Primitive('C') => quote!(::jni::sys::jchar),
+        JavaType::Primitive('S') => quote!(::jni::sys::jshort),
+        JavaType::Primitive('I') => quote!(::jni::sys::jint),
+        JavaType::Primitive('J') => quote!(::jni::sys::jlong),
+        JavaType::Primitive('F') => quote!(::jni::sys::jfloat),
+        JavaType::Primitive('D') => quote!(::jni::sys::jdouble),
+        JavaType::Primitive(c) => unreachable!("unexpected primitive type {}", c),
+        JavaType::Object("java/lang/String") => quote!(::jni::objects::JString<'static>),
+        JavaType::Object("java/lang/Class") => quote!(::jni::objects::JClass<'static>),
+        JavaType::Object("java/lang/Throwable") => quote!(::jni::objects::JThrowable<'static>),
+        JavaType::Object(_) | JavaType::Array => quote!(::jni::objects::JObject<'static>),
+    }
+}
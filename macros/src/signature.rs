@@ -0,0 +1,155 @@
+/// A Java type in a method signature.
+#[derive(Debug, PartialEq)]
+pub enum JavaType<'s> {
+    /// A primitive type, by its descriptor character (e.g. `I`).
+    Primitive(char),
+    /// An object type, by its internal class name (e.g. `java/lang/String`).
+    Object(&'s str),
+    /// An array type.
+    Array,
+}
+
+/// A parsed method signature such as `(I[Ljava/lang/String;)V`.
+#[derive(Debug, PartialEq)]
+pub struct MethodSignature<'s> {
+    /// The argument part of the signature, without the parentheses.
+    pub args_str: &'s str,
+    pub args: Vec<JavaType<'s>>,
+    /// The return type, `None` for `void`.
+    pub ret: Option<JavaType<'s>>,
+}
+
+/// Parses a method signature, checking that it is well-formed.
+pub fn parse_method(sig: &str) -> Result<MethodSignature<'_>, String> {
+    let invalid = || format!("invalid method signature: {}", sig);
+
+    let rest = sig.strip_prefix('(').ok_or_else(invalid)?;
+    let close = rest.find(')').ok_or_else(invalid)?;
+    let (args_str, ret) = (&rest[..close], &rest[close + 1..]);
+
+    let mut args = Vec::new();
+    let mut remaining = args_str;
+    while !remaining.is_empty() {
+        let (ty, rest) = parse_type(remaining).ok_or_else(invalid)?;
+        args.push(ty);
+        remaining = rest;
+    }
+
+    let ret = match ret {
+        "V" => None,
+        ret => match parse_type(ret) {
+            Some((ty, "")) => Some(ty),
+            _ => return Err(invalid()),
+        },
+    };
+
+    Ok(MethodSignature {
+        args_str,
+        args,
+        ret,
+    })
+}
+
+/// Parses a field type signature such as `Ljava/lang/String;`, checking that
+/// it is well-formed.
+pub fn parse_field(sig: &str) -> Result<JavaType<'_>, String> {
+    match parse_type(sig) {
+        Some((ty, "")) => Ok(ty),
+        _ => Err(format!("invalid field signature: {}", sig)),
+    }
+}
+
+// Parses a single field type descriptor, returning the remainder of the input.
+fn parse_type(input: &str) -> Option<(JavaType<'_>, &str)> {
+    let element = input.trim_start_matches('[');
+    let is_array = element.len() != input.len();
+
+    let (ty, rest) = match element.chars().next()? {
+        c @ ('Z' | 'B' | 'C' | 'S' | 'I' | 'J' | 'F' | 'D') => {
+            (JavaType::Primitive(c), &element[1..])
+        }
+        'L' => {
+            let end = element.find(';')?;
+            if end == 1 {
+                return None;
+            }
+            (JavaType::Object(&element[1..end]), &element[end + 1..])
+        }
+        _ => return None,
+    };
+
+    if is_array {
+        Some((JavaType::Array, rest))
+    } else {
+        Some((ty, rest))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(
+            parse_method("()V"),
+            Ok(MethodSignature {
+                args_str: "",
+                args: vec![],
+                ret: None
+            })
+        );
+        assert_eq!(
+            parse_method("(I[[JLjava/lang/String;Z)[I"),
+            Ok(MethodSignature {
+                args_str: "I[[JLjava/lang/String;Z",
+                args: vec![
+                    JavaType::Primitive('I'),
+                    JavaType::Array,
+                    JavaType::Object("java/lang/String"),
+                    JavaType::Primitive('Z'),
+                ],
+                ret: Some(JavaType::Array),
+            })
+        );
+        assert_eq!(
+            parse_method("(Ljava/lang/String;)Ljava/lang/Object;"),
+            Ok(MethodSignature {
+                args_str: "Ljava/lang/String;",
+                args: vec![JavaType::Object("java/lang/String")],
+                ret: Some(JavaType::Object("java/lang/Object")),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(parse_field("J"), Ok(JavaType::Primitive('J')));
+        assert_eq!(
+            parse_field("Ljava/util/List;"),
+            Ok(JavaType::Object("java/util/List"))
+        );
+        assert_eq!(parse_field("[I"), Ok(JavaType::Array));
+        for sig in &["", "V", "()V", "II", "Ljava/util/List", "L;"] {
+            assert!(parse_field(sig).is_err(), "{} should be invalid", sig);
+        }
+    }
+
+    #[test]
+    fn test_parse_method_invalid() {
+        for sig in &[
+            "",
+            "V",
+            "(I",
+            "(I)",
+            "(V)V",
+            "(X)V",
+            "(L;)V",
+            "([)V",
+            "(Ljava/lang/String)V",
+            "()VV",
+        ] {
+            assert!(parse_method(sig).is_err(), "{} should be invalid", sig);
+        }
+    }
+}
@@ -136,6 +136,19 @@
 //! Note that the type signature for our function is almost identical to the one
 //! from the generated header, aside from our lifetime-carrying arguments.
 //!
+//! With the `macros` feature enabled, the `#[jni_export]` attribute can
+//! generate the mangled `Java_*` function instead, and catches panics before
+//! they unwind into the JVM:
+//!
+//! ```rust,ignore
+//! #[jni_export(class = "HelloWorld")]
+//! fn hello<'a>(env: JNIEnv<'a>, _class: JClass<'a>, input: JString<'a>) -> JString<'a> {
+//!     let input: String = env.get_string(input).expect("Couldn't get java string!").into();
+//!     env.new_string(format!("Hello, {}!", input))
+//!         .expect("Couldn't create java string!")
+//! }
+//! ```
+//!
 //! ### Final steps
 //!
 //! That's it! Build your crate and try to run your Java class again.
@@ -224,6 +237,31 @@ mod wrapper {
     /// Optional thread attachment manager.
     mod executor;
     pub use self::executor::*;
+
+    /// Support for implementing native methods in Rust.
+    pub mod native;
+
+    /// Conversions between Rust values and Java objects.
+    pub mod convert;
+
+    /// Serde support for converting Rust values to and from graphs of Java
+    /// objects. Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub mod serde;
+
+    /// Typed bindings for Java classes, see `java_class!`.
+    #[doc(hidden)]
+    pub mod java_class;
 }
 
 pub use wrapper::*;
+
+/// Generates the exported, correctly mangled `Java_*` symbol for a native
+/// method implemented by a Rust function. Requires the `macros` feature.
+#[cfg(feature = "macros")]
+pub use jni_macros::jni_export;
+
+/// Checks a JNI method signature at compile time, creating a typed
+/// `signature::MethodSignature`. Requires the `macros` feature.
+#[cfg(feature = "macros")]
+pub use jni_macros::jni_sig;
@@ -0,0 +1,565 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{
+    errors::*,
+    objects::{AutoLocal, GlobalRef, JMap, JObject, JString, JValue},
+    sys::{jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+    JNIEnv,
+};
+
+/// Derive macros for `FromJava` and `IntoJava`. Requires the `macros`
+/// feature.
+#[cfg(feature = "macros")]
+pub use jni_macros::{FromJava, IntoJava};
+
+/// Conversion of a Rust value into a Java object.
+///
+/// Implemented for:
+/// - `String` and `&str`, as a `java.lang.String`,
+/// - the primitive types, boxed into their `java.lang` wrapper class (see
+///   `Boxable`),
+/// - `Option<T>`, with `None` as `null`,
+/// - `Vec<T>`, as a `java.util.ArrayList`,
+/// - tuples of up to 8 elements, as a `java.util.ArrayList` (as with the
+///   `serde` module),
+/// - `HashMap<K, V>`, as a `java.util.HashMap`,
+/// - `JObject`, `JString` and `&GlobalRef`, as a new local reference to the
+///   same object.
+///
+/// The returned object is always a new local reference (or `null`), which can
+/// be deleted once it has been used.
+pub trait IntoJava<'a> {
+    /// Converts the value into a Java object.
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>>;
+}
+
+/// Conversion of a Java object into a Rust value, the reverse of `IntoJava`.
+///
+/// Implemented for:
+/// - `String`, from a `java.lang.String`,
+/// - the primitive types, unboxed from their `java.lang` wrapper class (see
+///   `Boxable`),
+/// - `Option<T>`, with `null` as `None`,
+/// - `Vec<T>`, from any `java.util.Collection`,
+/// - tuples of up to 8 elements, from any `java.util.Collection` of that
+///   size, or `Error::Conversion` otherwise,
+/// - `HashMap<K, V>`, from any `java.util.Map`,
+/// - `GlobalRef`, as a new global reference to the object.
+///
+/// The values don't refer to the local reference they are converted from, so
+/// the local references to the elements of collections and maps are deleted
+/// once converted. Objects of the wrong class are rejected with
+/// `Error::WrongObjectType`, and `null` (other than for `Option`) with
+/// `Error::NullPtr`.
+pub trait FromJava<'a>: Sized {
+    /// Converts the Java object into a value.
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self>;
+}
+
+/// Conversion of a Java value, a primitive or an object, into a Rust value,
+/// e.g. the value of a field. Used for the fields of types deriving
+/// `FromJava`.
+///
+/// Implemented for the primitive types (as primitives, not boxed), and for
+/// `String`, `Option<T>`, `Vec<T>`, tuples, `HashMap<K, V>` and `GlobalRef`
+/// (as objects, see `FromJava`).
+pub trait FromJavaValue<'a>: Sized {
+    /// The JNI type signature of the Java value, e.g. `I` or
+    /// `Ljava/lang/String;`.
+    const SIGNATURE: &'static str;
+
+    /// Converts the Java value. The local reference to an object isn't
+    /// deleted.
+    fn from_jvalue(env: &JNIEnv<'a>, value: JValue<'a>) -> Result<Self>;
+}
+
+/// Conversion of a Rust value into a Java value, a primitive or an object,
+/// e.g. to set a field, the reverse of `FromJavaValue`. Used for the fields of
+/// types deriving `IntoJava`.
+pub trait IntoJavaValue<'a> {
+    /// The JNI type signature of the Java value, e.g. `I` or
+    /// `Ljava/lang/String;`.
+    const SIGNATURE: &'static str;
+
+    /// Converts the value. An object is a new local reference (or `null`).
+    fn into_jvalue(self, env: &JNIEnv<'a>) -> Result<JValue<'a>>;
+}
+
+/// The arguments of a call with converted arguments, e.g.
+/// `JNIEnv#call_method_converted`: a tuple of values implementing `IntoJava`.
+pub trait IntoJavaArgs<'a> {
+    /// Converts the values into Java objects, whose local references are
+    /// deleted when they are dropped.
+    fn into_java_args<'b>(self, env: &'b JNIEnv<'a>) -> Result<Vec<AutoLocal<'a, 'b>>>;
+}
+
+impl<'a> IntoJava<'a> for JObject<'a> {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        env.new_local_ref::<JObject>(self)
+    }
+}
+
+impl<'a> IntoJava<'a> for JString<'a> {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        env.new_local_ref::<JObject>(self.into())
+    }
+}
+
+impl<'a> IntoJava<'a> for &GlobalRef {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        env.new_local_ref::<JObject>(JObject::from(self.as_obj().into_inner()))
+    }
+}
+
+impl<'a> IntoJava<'a> for &str {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        Ok(env.new_string(self)?.into())
+    }
+}
+
+impl<'a> IntoJava<'a> for String {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        self.as_str().into_java(env)
+    }
+}
+
+impl<'a, T: IntoJava<'a>> IntoJava<'a> for Option<T> {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        match self {
+            Some(value) => value.into_java(env),
+            None => Ok(JObject::null()),
+        }
+    }
+}
+
+impl<'a, T: IntoJava<'a>> IntoJava<'a> for Vec<T> {
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        let list = env.new_object(
+            "java/util/ArrayList",
+            "(I)V",
+            &[(self.len() as jint).into()],
+        )?;
+        let list = env.get_list(list)?;
+        for elem in self {
+            let elem = env.auto_local(elem.into_java(env)?);
+            list.add(elem.as_obj())?;
+        }
+        Ok(list.into())
+    }
+}
+
+impl<'a, K, V, S> IntoJava<'a> for HashMap<K, V, S>
+where
+    K: IntoJava<'a>,
+    V: IntoJava<'a>,
+{
+    fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+        let map = env.new_object("java/util/HashMap", "()V", &[])?;
+        let map = JMap::from_env(env, map)?;
+        for (key, value) in self {
+            let key = env.auto_local(key.into_java(env)?);
+            let value = env.auto_local(value.into_java(env)?);
+            let previous = map.put(key.as_obj(), value.as_obj())?;
+            if let Some(previous) = previous {
+                env.delete_local_ref(previous)?;
+            }
+        }
+        Ok(map.into())
+    }
+}
+
+impl<'a> FromJava<'a> for String {
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        non_null!(obj, "String::from_java obj argument");
+        if !env.is_instance_of(obj, "java/lang/String")? {
+            return Err(Error::WrongObjectType("java.lang.String"));
+        }
+        Ok(env.get_string(obj.into())?.into())
+    }
+}
+
+impl<'a> FromJava<'a> for GlobalRef {
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        env.new_global_ref(obj)
+    }
+}
+
+impl<'a, T: FromJava<'a>> FromJava<'a> for Option<T> {
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        if obj.is_null() {
+            Ok(None)
+        } else {
+            T::from_java(env, obj).map(Some)
+        }
+    }
+}
+
+impl<'a, T: FromJava<'a>> FromJava<'a> for Vec<T> {
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        let collection = env.get_collection(obj)?;
+        let mut vec = Vec::with_capacity(collection.size()?.max(0) as usize);
+        let iter = collection.iter()?;
+        while let Some(elem) = iter.next_elem()? {
+            let elem = env.auto_local(elem);
+            vec.push(T::from_java(env, elem.as_obj())?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'a, K, V, S> FromJava<'a> for HashMap<K, V, S>
+where
+    K: FromJava<'a> + Eq + Hash,
+    V: FromJava<'a>,
+    S: BuildHasher + Default,
+{
+    fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+        non_null!(obj, "HashMap::from_java obj argument");
+        if !env.is_instance_of(obj, "java/util/Map")? {
+            return Err(Error::WrongObjectType("java.util.Map"));
+        }
+        let map = env.get_map(obj)?;
+        let mut result =
+            HashMap::with_capacity_and_hasher(map.size()?.max(0) as usize, S::default());
+        let iter = map.iter()?;
+        while let Some((key, value)) = iter.next_entry()? {
+            let key = env.auto_local(key);
+            let value = env.auto_local(value);
+            result.insert(
+                K::from_java(env, key.as_obj())?,
+                V::from_java(env, value.as_obj())?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+macro_rules! java_boxed {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl<'a> IntoJava<'a> for $ty {
+                fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+                    env.box_value(self)
+                }
+            }
+
+            impl<'a> FromJava<'a> for $ty {
+                fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+                    env.unbox_value(obj)
+                }
+            }
+        )*
+    };
+}
+
+java_boxed!(bool, jbyte, jchar, jshort, jint, jlong, jfloat, jdouble);
+
+macro_rules! java_primitive_value {
+    ( $( $ty:ty, $sig:literal, $unwrap:ident );* ) => {
+        $(
+            impl<'a> FromJavaValue<'a> for $ty {
+                const SIGNATURE: &'static str = $sig;
+
+                fn from_jvalue(_env: &JNIEnv<'a>, value: JValue<'a>) -> Result<Self> {
+                    value.$unwrap()
+                }
+            }
+
+            impl<'a> IntoJavaValue<'a> for $ty {
+                const SIGNATURE: &'static str = $sig;
+
+                fn into_jvalue(self, _env: &JNIEnv<'a>) -> Result<JValue<'a>> {
+                    Ok(JValue::from(self))
+                }
+            }
+        )*
+    };
+}
+
+java_primitive_value!(
+    bool, "Z", z;
+    jbyte, "B", b;
+    jchar, "C", c;
+    jshort, "S", s;
+    jint, "I", i;
+    jlong, "J", j;
+    jfloat, "F", f;
+    jdouble, "D", d
+);
+
+// Implements `FromJavaValue` and `IntoJavaValue` for types converted from and
+// into objects with `FromJava` and `IntoJava`.
+macro_rules! java_object_value {
+    ( [$($generics:tt)*] $ty:ty, $sig:expr, [$($from_bounds:tt)*], [$($into_bounds:tt)*] ) => {
+        impl<'a, $($generics)*> FromJavaValue<'a> for $ty
+        where
+            $($from_bounds)*
+        {
+            const SIGNATURE: &'static str = $sig;
+
+            fn from_jvalue(env: &JNIEnv<'a>, value: JValue<'a>) -> Result<Self> {
+                Self::from_java(env, value.l()?)
+            }
+        }
+
+        impl<'a, $($generics)*> IntoJavaValue<'a> for $ty
+        where
+            $($into_bounds)*
+        {
+            const SIGNATURE: &'static str = $sig;
+
+            fn into_jvalue(self, env: &JNIEnv<'a>) -> Result<JValue<'a>> {
+                Ok(JValue::Object(self.into_java(env)?))
+            }
+        }
+    };
+}
+
+java_object_value!([] String, "Ljava/lang/String;", [], []);
+java_object_value!(
+    [T] Vec<T>,
+    "Ljava/util/List;",
+    [T: FromJava<'a>],
+    [T: IntoJava<'a>]
+);
+java_object_value!(
+    [K, V, S] HashMap<K, V, S>,
+    "Ljava/util/Map;",
+    [K: FromJava<'a> + Eq + Hash, V: FromJava<'a>, S: BuildHasher + Default],
+    [K: IntoJava<'a>, V: IntoJava<'a>]
+);
+// An `Option` of a primitive is a boxed object.
+java_object_value!(
+    [T] Option<T>,
+    object_signature(T::SIGNATURE),
+    [T: FromJava<'a> + FromJavaValue<'a>],
+    [T: IntoJava<'a> + IntoJavaValue<'a>]
+);
+
+macro_rules! java_tuple {
+    ($len:literal: $($elem:ident),+) => {
+        impl<'a, $($elem),+> IntoJava<'a> for ($($elem,)+)
+        where
+            $($elem: IntoJava<'a>),+
+        {
+            #[allow(non_snake_case)]
+            fn into_java(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+                let ($($elem,)+) = self;
+                let list = env.new_object("java/util/ArrayList", "(I)V", &[$len.into()])?;
+                let list = env.get_list(list)?;
+                $(
+                    let elem = env.auto_local($elem.into_java(env)?);
+                    list.add(elem.as_obj())?;
+                )+
+                Ok(list.into())
+            }
+        }
+
+        impl<'a, $($elem),+> FromJava<'a> for ($($elem,)+)
+        where
+            $($elem: FromJava<'a>),+
+        {
+            fn from_java(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+                let collection = env.get_collection(obj)?;
+                let size = collection.size()?;
+                if size != $len {
+                    return Err(tuple_size_error($len, size));
+                }
+                let iter = collection.iter()?;
+                Ok(($(
+                    {
+                        // The collection may have been changed concurrently.
+                        let elem = iter
+                            .next_elem()?
+                            .ok_or_else(|| tuple_size_error($len, size))?;
+                        let elem = env.auto_local(elem);
+                        $elem::from_java(env, elem.as_obj())?
+                    },
+                )+))
+            }
+        }
+
+        java_object_value!(
+            [$($elem),+] ($($elem,)+),
+            "Ljava/util/List;",
+            [$($elem: FromJava<'a>),+],
+            [$($elem: IntoJava<'a>),+]
+        );
+    };
+}
+
+fn tuple_size_error(len: jint, size: jint) -> Error {
+    Error::Conversion(format!(
+        "expected a collection of {} elements for a tuple, got {}",
+        len, size
+    ))
+}
+
+java_tuple!(1: T1);
+java_tuple!(2: T1, T2);
+java_tuple!(3: T1, T2, T3);
+java_tuple!(4: T1, T2, T3, T4);
+java_tuple!(5: T1, T2, T3, T4, T5);
+java_tuple!(6: T1, T2, T3, T4, T5, T6);
+java_tuple!(7: T1, T2, T3, T4, T5, T6, T7);
+java_tuple!(8: T1, T2, T3, T4, T5, T6, T7, T8);
+
+impl<'a> FromJavaValue<'a> for GlobalRef {
+    const SIGNATURE: &'static str = "Ljava/lang/Object;";
+
+    fn from_jvalue(env: &JNIEnv<'a>, value: JValue<'a>) -> Result<Self> {
+        Self::from_java(env, value.l()?)
+    }
+}
+
+impl<'a> IntoJavaValue<'a> for GlobalRef {
+    const SIGNATURE: &'static str = "Ljava/lang/Object;";
+
+    fn into_jvalue(self, env: &JNIEnv<'a>) -> Result<JValue<'a>> {
+        Ok(JValue::Object((&self).into_java(env)?))
+    }
+}
+
+// The signature of the boxed type of a primitive, or the given signature.
+const fn object_signature(sig: &'static str) -> &'static str {
+    match sig.as_bytes() {
+        b"Z" => "Ljava/lang/Boolean;",
+        b"B" => "Ljava/lang/Byte;",
+        b"C" => "Ljava/lang/Character;",
+        b"S" => "Ljava/lang/Short;",
+        b"I" => "Ljava/lang/Integer;",
+        b"J" => "Ljava/lang/Long;",
+        b"F" => "Ljava/lang/Float;",
+        b"D" => "Ljava/lang/Double;",
+        _ => sig,
+    }
+}
+
+macro_rules! into_java_args {
+    ($($arg:ident),*) => {
+        impl<'a, $($arg),*> IntoJavaArgs<'a> for ($($arg,)*)
+        where
+            $($arg: IntoJava<'a>),*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn into_java_args<'b>(self, env: &'b JNIEnv<'a>) -> Result<Vec<AutoLocal<'a, 'b>>> {
+                let ($($arg,)*) = self;
+                Ok(vec![$(env.auto_local($arg.into_java(env)?)),*])
+            }
+        }
+    };
+}
+
+into_java_args!();
+into_java_args!(A1);
+into_java_args!(A1, A2);
+into_java_args!(A1, A2, A3);
+into_java_args!(A1, A2, A3, A4);
+into_java_args!(A1, A2, A3, A4, A5);
+into_java_args!(A1, A2, A3, A4, A5, A6);
+into_java_args!(A1, A2, A3, A4, A5, A6, A7);
+into_java_args!(A1, A2, A3, A4, A5, A6, A7, A8);
+
+/// Support code for the `FromJava` and `IntoJava` derive macros.
+#[doc(hidden)]
+pub mod derive_support {
+    use super::{FromJavaValue, IntoJavaValue};
+    use crate::{
+        errors::*,
+        objects::{JObject, JValue},
+        JNIEnv,
+    };
+
+    pub fn check_instance(
+        env: &JNIEnv,
+        obj: JObject,
+        class: &str,
+        binary_name: &'static str,
+    ) -> Result<()> {
+        non_null!(obj, "FromJava obj argument");
+        if !env.is_instance_of(obj, class)? {
+            return Err(Error::WrongObjectType(binary_name));
+        }
+        Ok(())
+    }
+
+    pub fn get_field<'a, T: FromJavaValue<'a>>(
+        env: &JNIEnv<'a>,
+        obj: JObject<'a>,
+        name: &str,
+        sig: &str,
+    ) -> Result<T> {
+        let value = env.get_field(obj, name, sig)?;
+        from_jvalue(env, value)
+    }
+
+    pub fn call_accessor<'a, T: FromJavaValue<'a>>(
+        env: &JNIEnv<'a>,
+        obj: JObject<'a>,
+        name: &str,
+        sig: &str,
+    ) -> Result<T> {
+        let value = env.call_method(obj, name, format!("(){}", sig), &[])?;
+        from_jvalue(env, value)
+    }
+
+    // Converts the value, deleting the local reference to an object.
+    fn from_jvalue<'a, T: FromJavaValue<'a>>(env: &JNIEnv<'a>, value: JValue<'a>) -> Result<T> {
+        let converted = T::from_jvalue(env, value);
+        if let JValue::Object(obj) = value {
+            env.delete_local_ref(obj)?;
+        }
+        converted
+    }
+
+    pub fn set_field<'a, T: IntoJavaValue<'a>>(
+        env: &JNIEnv<'a>,
+        obj: JObject<'a>,
+        name: &str,
+        sig: &str,
+        value: T,
+    ) -> Result<()> {
+        let value = value.into_jvalue(env)?;
+        let res = env.set_field(obj, name, sig, value);
+        if let JValue::Object(obj) = value {
+            env.delete_local_ref(obj)?;
+        }
+        res
+    }
+
+    pub fn new_record<'a>(
+        env: &JNIEnv<'a>,
+        class: &str,
+        sigs: &[&str],
+        args: Vec<JValue<'a>>,
+    ) -> Result<JObject<'a>> {
+        let ctor_sig = format!("({})V", sigs.concat());
+        let res = env.new_object(class, ctor_sig, &args);
+        for arg in args {
+            if let JValue::Object(obj) = arg {
+                env.delete_local_ref(obj)?;
+            }
+        }
+        res
+    }
+
+    pub fn enum_name(env: &JNIEnv, obj: JObject) -> Result<String> {
+        let name = env
+            .call_method(obj, "name", "()Ljava/lang/String;", &[])?
+            .l()?;
+        let name = env.auto_local(name);
+        Ok(env.get_string(name.as_obj().into())?.into())
+    }
+
+    pub fn enum_constant<'a>(
+        env: &JNIEnv<'a>,
+        class: &str,
+        name: &str,
+        sig: &str,
+    ) -> Result<JObject<'a>> {
+        env.get_static_field(class, name, sig)?.l()
+    }
+}
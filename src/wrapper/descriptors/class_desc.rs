@@ -13,6 +13,10 @@ where
     fn lookup(self, env: &JNIEnv<'a>) -> Result<JClass<'a>> {
         env.find_class(self)
     }
+
+    fn into_class_name(self) -> std::result::Result<JNIString, Self> {
+        Ok(self.into())
+    }
 }
 
 impl<'a, 'b> Desc<'a, JClass<'a>> for JObject<'b> {
@@ -1,4 +1,4 @@
-use crate::{errors::*, JNIEnv};
+use crate::{errors::*, strings::JNIString, JNIEnv};
 
 /// Trait for things that can be looked up through the JNI via a descriptor.
 /// This will be something like the fully-qualified class name
@@ -8,6 +8,17 @@ use crate::{errors::*, JNIEnv};
 pub trait Desc<'a, T> {
     /// Look up the concrete type from the JVM.
     fn lookup(self, _: &JNIEnv<'a>) -> Result<T>;
+
+    /// Returns the class name of a class descriptor given by name, or the
+    /// descriptor itself otherwise. Used by the ID cache to look IDs up
+    /// without looking the class up first.
+    #[doc(hidden)]
+    fn into_class_name(self) -> std::result::Result<JNIString, Self>
+    where
+        Self: Sized,
+    {
+        Err(self)
+    }
 }
 
 impl<'a, T> Desc<'a, T> for T {
@@ -1,5 +1,5 @@
 use crate::{
-    descriptors::Desc,
+    descriptors::{id_cache, Desc},
     errors::*,
     objects::{JClass, JFieldID, JStaticFieldID},
     strings::JNIString,
@@ -13,7 +13,9 @@ where
     V: Into<JNIString>,
 {
     fn lookup(self, env: &JNIEnv<'a>) -> Result<JFieldID<'a>> {
-        env.get_field_id(self.0, self.1, self.2)
+        id_cache::lookup_id(env, self.0, self.1, self.2, |class, name, sig| {
+            env.get_field_id(class, name, sig)
+        })
     }
 }
 
@@ -24,6 +26,8 @@ where
     V: Into<JNIString>,
 {
     fn lookup(self, env: &JNIEnv<'a>) -> Result<JStaticFieldID<'a>> {
-        env.get_static_field_id(self.0, self.1, self.2)
+        id_cache::lookup_id(env, self.0, self.1, self.2, |class, name, sig| {
+            env.get_static_field_id(class, name, sig)
+        })
     }
 }
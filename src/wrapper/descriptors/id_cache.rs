@@ -0,0 +1,332 @@
+use std::{
+    collections::HashMap,
+    os::raw::c_void,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use log::debug;
+
+use crate::{
+    descriptors::Desc,
+    errors::*,
+    objects::{GlobalRef, JClass, JFieldID, JMethodID, JObject, JStaticFieldID, JStaticMethodID},
+    signature::ReturnType,
+    strings::JNIString,
+    sys::{jfieldID, jmethodID},
+    wrapper::jnienv::thread_class_loader,
+    JNIEnv,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// The entries of a key are shared, so that they can be looked at without
+// holding the lock or copying them.
+type Cache = HashMap<Key, Arc<[Entry]>>;
+
+static CACHE: Mutex<Option<Cache>> = Mutex::new(None);
+
+/// Enables or disables the process-wide cache of method and field IDs.
+/// Disabled by default.
+///
+/// When enabled, the IDs looked up through the `(class, name, sig)`
+/// descriptors, as done by `call_method`, `get_field`, `set_rust_field` and
+/// friends, are cached by class, name and signature, so that only the class
+/// needs to be looked up on subsequent calls. Disabling the cache clears it.
+///
+/// The cache keeps a `GlobalRef` to each class it has IDs for, which keeps the
+/// IDs valid, but also prevents the class loaders of these classes from being
+/// unloaded. Use `invalidate_class_loader` before dropping the last reference
+/// to a class loader that should be unloaded.
+pub fn set_id_cache_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        clear_id_cache();
+    }
+}
+
+/// Returns whether the method and field ID cache is enabled, see
+/// `set_id_cache_enabled`.
+pub fn is_id_cache_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Removes all the IDs from the cache, and releases the references it holds to
+/// their classes.
+pub fn clear_id_cache() {
+    // Dropping the `GlobalRef`s may attach the thread, so it's done without
+    // holding the lock.
+    let removed = lock_cache().take();
+    drop(removed);
+}
+
+/// Removes the IDs of the classes defined by `loader` from the cache, and
+/// releases the references it holds to these classes, so that the class loader
+/// can be unloaded. A null `loader` stands for the bootstrap class loader.
+pub fn invalidate_class_loader(env: &JNIEnv, loader: JObject) -> Result<()> {
+    // Looked up directly rather than through a descriptor, which would use
+    // the cache.
+    let get_class_loader: JMethodID = env.get_method_id(
+        "java/lang/Class",
+        "getClassLoader",
+        "()Ljava/lang/ClassLoader;",
+    )?;
+
+    // The cache isn't locked while calling into Java. Should that fail, the
+    // entries that weren't checked yet are put back into the cache as is.
+    let entries = match lock_cache().take() {
+        Some(entries) => entries,
+        None => return Ok(()),
+    };
+
+    let mut result = Ok(());
+    let mut kept = Vec::new();
+    let mut removed = 0;
+    for (key, candidates) in entries {
+        let mut kept_candidates = Vec::new();
+        for entry in candidates.iter() {
+            if result.is_ok() {
+                match is_defined_by(env, entry, get_class_loader, loader) {
+                    Ok(true) => {
+                        removed += 1;
+                        continue;
+                    }
+                    Ok(false) => {}
+                    Err(e) => result = Err(e),
+                }
+            }
+            kept_candidates.push(entry.clone());
+        }
+        if !kept_candidates.is_empty() {
+            kept.push((key, kept_candidates));
+        }
+    }
+
+    if is_id_cache_enabled() {
+        let mut cache = lock_cache();
+        let cache = cache.get_or_insert_with(HashMap::new);
+        for (key, candidates) in kept {
+            add_entries(cache, key, candidates);
+        }
+    }
+    debug!("invalidated {} cached IDs", removed);
+    result
+}
+
+// Whether the class of an entry, or the class loader its class name was
+// resolved with, is `loader`.
+fn is_defined_by(
+    env: &JNIEnv,
+    entry: &Entry,
+    get_class_loader: JMethodID,
+    loader: JObject,
+) -> Result<bool> {
+    if let Some(name_loader) = &entry.loader {
+        if env.is_same_object(name_loader.as_obj(), loader)? {
+            return Ok(true);
+        }
+    }
+    let class_loader = env.auto_local(
+        env.call_method_unchecked(
+            entry.class.as_obj().into_inner(),
+            get_class_loader,
+            ReturnType::Object,
+            &[],
+        )?
+        .l()?,
+    );
+    env.is_same_object(&class_loader, loader)
+}
+
+// The kind of ID, as the same name and signature can be used for e.g. both a
+// method and a static method of different classes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum IdKind {
+    Method,
+    StaticMethod,
+    Field,
+    StaticField,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct Key {
+    kind: IdKind,
+    // The name of the class, for the IDs looked up with a class name rather
+    // than with a class.
+    class_name: Option<Vec<u8>>,
+    name: Vec<u8>,
+    sig: Vec<u8>,
+}
+
+#[derive(Clone)]
+struct Entry {
+    class: GlobalRef,
+    // For the IDs cached by class name, the class loader `find_class` used on
+    // the thread.
+    loader: Option<GlobalRef>,
+    id: RawId,
+}
+
+// A `jmethodID` or `jfieldID`.
+#[derive(Clone, Copy)]
+struct RawId(*mut c_void);
+
+// IDs are valid across threads (not tied to a `JNIEnv`), as long as their
+// class is not unloaded, which the `GlobalRef` of the entry prevents.
+unsafe impl Send for RawId {}
+unsafe impl Sync for RawId {}
+
+fn lock_cache() -> MutexGuard<'static, Option<Cache>> {
+    // The cache is left consistent if a panic happens while it's locked.
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A method or field ID that can be cached.
+pub(crate) trait CachedId: Copy {
+    /// The kind of ID.
+    const KIND: IdKind;
+
+    /// Converts the ID into a raw pointer for the cache.
+    fn into_raw(self) -> *mut c_void;
+
+    /// Converts a raw pointer from the cache back into an ID.
+    fn from_raw(raw: *mut c_void) -> Self;
+}
+
+macro_rules! cached_id {
+    ($ty:ty, $raw:ty, $kind:ident) => {
+        impl CachedId for $ty {
+            const KIND: IdKind = IdKind::$kind;
+
+            fn into_raw(self) -> *mut c_void {
+                self.into_inner() as *mut c_void
+            }
+
+            fn from_raw(raw: *mut c_void) -> Self {
+                Self::from(raw as $raw)
+            }
+        }
+    };
+}
+
+cached_id!(JMethodID, jmethodID, Method);
+cached_id!(JStaticMethodID, jmethodID, StaticMethod);
+cached_id!(JFieldID<'_>, jfieldID, Field);
+cached_id!(JStaticFieldID<'_>, jfieldID, StaticField);
+
+/// Looks up an ID in the cache if it is enabled, and with `lookup` otherwise
+/// or if it isn't cached yet.
+///
+/// IDs looked up with a class name are cached by that name along with the
+/// class loader `find_class` uses on the thread (see
+/// `Executor::new_with_class_loader`), so that the class doesn't need to be
+/// looked up on subsequent calls. Without one, `FindClass` resolves the name
+/// with the class loader of the calling method, which may differ from call to
+/// call, so the class is looked up and compared like a given class.
+pub(crate) fn lookup_id<'a, 'c, T, U, V, I, F>(
+    env: &JNIEnv<'a>,
+    class: T,
+    name: U,
+    sig: V,
+    lookup: F,
+) -> Result<I>
+where
+    T: Desc<'a, JClass<'c>>,
+    U: Into<JNIString>,
+    V: Into<JNIString>,
+    I: CachedId,
+    F: FnOnce(JClass<'c>, JNIString, JNIString) -> Result<I>,
+{
+    let name = name.into();
+    let sig = sig.into();
+    if !is_id_cache_enabled() {
+        return lookup(class.lookup(env)?, name, sig);
+    }
+
+    let mut key = Key {
+        kind: I::KIND,
+        class_name: None,
+        name: name.to_bytes().to_vec(),
+        sig: sig.to_bytes().to_vec(),
+    };
+
+    let (class, loader) = match class.into_class_name() {
+        Ok(class_name) => {
+            let loader = thread_class_loader();
+            if let Some(loader) = &loader {
+                key.class_name = Some(class_name.to_bytes().to_vec());
+                if let Some(id) = find(&key, |entry| match &entry.loader {
+                    Some(entry_loader) => {
+                        env.is_same_object(entry_loader.as_obj(), loader.as_obj())
+                    }
+                    None => Ok(false),
+                })? {
+                    return Ok(I::from_raw(id.0));
+                }
+            }
+            // The class found by name lives as long as the `JNIEnv`, which
+            // `lookup` expects of classes given by name.
+            let class = env.find_class(class_name)?;
+            (JClass::from(class.into_inner()), loader)
+        }
+        Err(class) => (class.lookup(env)?, None),
+    };
+
+    if loader.is_none() {
+        if let Some(id) = find(&key, |entry| {
+            env.is_same_object(entry.class.as_obj(), class)
+        })? {
+            return Ok(I::from_raw(id.0));
+        }
+    }
+
+    let id = lookup(class, name, sig)?;
+    let entry = Entry {
+        class: env.new_global_ref(class)?,
+        loader,
+        id: RawId(id.into_raw()),
+    };
+
+    // The ID may have been cached by another thread in the meantime, in which
+    // case it's cached twice, which is harmless. The objects aren't compared
+    // while the cache is locked, as that calls into Java.
+    let mut cache = lock_cache();
+    if is_id_cache_enabled() {
+        add_entries(cache.get_or_insert_with(HashMap::new), key, vec![entry]);
+    } else {
+        // Dropping the `GlobalRef`s may attach the thread, so don't hold the lock.
+        drop(cache);
+        drop(entry);
+    }
+    Ok(id)
+}
+
+// Adds entries to the ones of `key`. The previous entries are shared with
+// the new ones, so no `GlobalRef` is released while the cache is locked.
+fn add_entries(cache: &mut Cache, key: Key, entries: Vec<Entry>) {
+    let candidates = cache.entry(key).or_insert_with(|| Arc::new([]));
+    let mut all = candidates.to_vec();
+    all.extend(entries);
+    *candidates = all.into();
+}
+
+// Finds the ID of the first entry of `key` that `matches`. The entries are
+// shared out of the cache, so that `matches` is called without holding the
+// lock.
+fn find<M>(key: &Key, mut matches: M) -> Result<Option<RawId>>
+where
+    M: FnMut(&Entry) -> Result<bool>,
+{
+    let candidates = match lock_cache().as_ref().and_then(|cache| cache.get(key)) {
+        Some(candidates) => Arc::clone(candidates),
+        None => return Ok(None),
+    };
+    for entry in candidates.iter() {
+        if matches(entry)? {
+            return Ok(Some(entry.id));
+        }
+    }
+    Ok(None)
+}
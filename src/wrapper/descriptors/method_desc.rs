@@ -1,5 +1,5 @@
 use crate::{
-    descriptors::Desc,
+    descriptors::{id_cache, Desc},
     errors::*,
     objects::{JClass, JMethodID, JStaticMethodID},
     strings::JNIString,
@@ -13,7 +13,9 @@ where
     V: Into<JNIString>,
 {
     fn lookup(self, env: &JNIEnv<'a>) -> Result<JMethodID> {
-        env.get_method_id(self.0, self.1, self.2)
+        id_cache::lookup_id(env, self.0, self.1, self.2, |class, name, sig| {
+            env.get_method_id(class, name, sig)
+        })
     }
 }
 
@@ -34,6 +36,8 @@ where
     V: Into<JNIString>,
 {
     fn lookup(self, env: &JNIEnv<'a>) -> Result<JStaticMethodID> {
-        env.get_static_method_id(self.0, self.1, self.2)
+        id_cache::lookup_id(env, self.0, self.1, self.2, |class, name, sig| {
+            env.get_static_method_id(class, name, sig)
+        })
     }
 }
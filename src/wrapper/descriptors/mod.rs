@@ -12,3 +12,6 @@ pub use self::field_desc::*;
 
 mod exception_desc;
 pub use self::exception_desc::*;
+
+mod id_cache;
+pub use self::id_cache::*;
@@ -1,9 +1,18 @@
 #![allow(missing_docs)]
 
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
 use thiserror::Error;
 
 use crate::sys;
 use crate::wrapper::signature::TypeSignature;
+use crate::{
+    objects::{GlobalRef, JThrowable},
+    JNIEnv,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -11,6 +20,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     #[error("Invalid JValue type cast: {0}. Actual type: {1}")]
     WrongJValueType(&'static str, &'static str),
+    #[error("Object is not an instance of {0}")]
+    WrongObjectType(&'static str),
     #[error("Invalid constructor return type (must be void)")]
     InvalidCtorReturn,
     #[error("Invalid number of arguments passed to java method: {0}")]
@@ -21,8 +32,14 @@ pub enum Error {
     FieldNotFound { name: String, sig: String },
     #[error("Java exception was thrown")]
     JavaException,
+    /// A Java exception was thrown, and captured (and cleared) because
+    /// exception capture is enabled, see `set_exception_capture`.
+    #[error("Java exception was thrown: {0}")]
+    CaughtJavaException(Box<JavaExceptionDetails>),
     #[error("JNIEnv null method pointer for {0}")]
     JNIEnvMethodNotFound(&'static str),
+    #[error("Array index out of bounds: {0}")]
+    IndexOutOfBounds(sys::jsize),
     #[error("Null pointer in {0}")]
     NullPtr(&'static str),
     #[error("Null pointer deref in {0}")]
@@ -39,6 +56,10 @@ pub enum Error {
     ParseFailed(#[source] combine::error::StringStreamError, String),
     #[error("JNI call failed")]
     JniCall(#[source] JniError),
+    /// A value couldn't be converted into, or from, a Java object, e.g. by
+    /// the `serde` module.
+    #[error("Conversion failed: {0}")]
+    Conversion(String),
 }
 
 #[derive(Debug, Error)]
@@ -86,4 +107,147 @@ pub struct Exception {
 
 pub trait ToException {
     fn to_exception(&self) -> Exception;
+
+    /// Throws this error as a Java exception.
+    ///
+    /// By default, this throws a new exception as described by
+    /// `to_exception`.
+    fn throw_exception(&self, env: &JNIEnv) -> Result<()> {
+        let exception = self.to_exception();
+        env.throw_new(exception.class, exception.msg)
+    }
+}
+
+impl ToException for Exception {
+    fn to_exception(&self) -> Exception {
+        Exception {
+            class: self.class.clone(),
+            msg: self.msg.clone(),
+        }
+    }
+}
+
+impl ToException for Error {
+    /// Maps the error to the closest matching Java exception or error, with
+    /// the error message as the exception message.
+    ///
+    /// `Error::JavaException` means an exception is already pending, so it
+    /// maps to a generic `java.lang.RuntimeException` that only gets thrown if
+    /// the original exception has been cleared in the meantime.
+    fn to_exception(&self) -> Exception {
+        let class = match self {
+            Error::NullPtr(_) | Error::NullDeref(_) => "java/lang/NullPointerException",
+            Error::WrongObjectType(_) => "java/lang/ClassCastException",
+            Error::IndexOutOfBounds(_) => "java/lang/ArrayIndexOutOfBoundsException",
+            Error::WrongJValueType(_, _)
+            | Error::InvalidCtorReturn
+            | Error::InvalidArgList(_)
+            | Error::ParseFailed(_, _)
+            | Error::Conversion(_) => "java/lang/IllegalArgumentException",
+            Error::MethodNotFound { .. } => "java/lang/NoSuchMethodError",
+            Error::FieldNotFound { .. } => "java/lang/NoSuchFieldError",
+            Error::JniCall(JniError::NoMemory) => "java/lang/OutOfMemoryError",
+            Error::FieldAlreadySet(_) | Error::TryLock => "java/lang/IllegalStateException",
+            Error::CaughtJavaException(details) => {
+                return Exception {
+                    class: details.class.replace('.', "/"),
+                    msg: details.message.clone().unwrap_or_default(),
+                }
+            }
+            _ => "java/lang/RuntimeException",
+        };
+        Exception {
+            class: class.to_string(),
+            msg: self.to_string(),
+        }
+    }
+
+    /// Rethrows the original exception for `Error::CaughtJavaException`,
+    /// and throws the exception described by `to_exception` otherwise.
+    fn throw_exception(&self, env: &JNIEnv) -> Result<()> {
+        match self {
+            Error::CaughtJavaException(details) => details.rethrow(env),
+            _ => {
+                let exception = self.to_exception();
+                env.throw_new(exception.class, exception.msg)
+            }
+        }
+    }
+}
+
+static EXCEPTION_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables exception capture for the whole process. Disabled by
+/// default.
+///
+/// When enabled, a Java exception thrown by a JNI call is cleared and returned
+/// as `Error::CaughtJavaException`, which holds the throwable along with its
+/// class name, message, cause chain and stack trace, instead of being left
+/// pending with a plain `Error::JavaException`. The exception can be thrown
+/// again with `JavaExceptionDetails::rethrow`.
+///
+/// If the details can't be captured (e.g. because another exception is
+/// thrown while doing so), the original exception is left pending and
+/// `Error::JavaException` is returned as usual.
+pub fn set_exception_capture(enabled: bool) {
+    EXCEPTION_CAPTURE.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether exception capture is enabled, see `set_exception_capture`.
+pub fn is_exception_capture_enabled() -> bool {
+    EXCEPTION_CAPTURE.load(Ordering::Relaxed)
+}
+
+/// The details of a Java exception captured in `Error::CaughtJavaException`.
+#[derive(Debug)]
+pub struct JavaExceptionDetails {
+    pub(crate) throwable: GlobalRef,
+    /// The binary name of the class of the exception, e.g.
+    /// `java.lang.IllegalStateException`.
+    pub class: String,
+    /// The message of the exception, if any.
+    pub message: Option<String>,
+    /// The chain of causes of the exception, starting with its direct cause.
+    pub causes: Vec<JavaExceptionCause>,
+    /// The stack trace of the exception, as printed by
+    /// `Throwable#printStackTrace`, including the stack traces of the causes.
+    pub stack_trace: String,
+}
+
+/// A cause of a captured Java exception, see `JavaExceptionDetails`.
+#[derive(Debug)]
+pub struct JavaExceptionCause {
+    /// The binary name of the class of the cause.
+    pub class: String,
+    /// The message of the cause, if any.
+    pub message: Option<String>,
+}
+
+impl JavaExceptionDetails {
+    /// Returns a global reference to the captured throwable.
+    pub fn throwable(&self) -> &GlobalRef {
+        &self.throwable
+    }
+
+    /// Throws the captured exception again, e.g. to let it propagate to the
+    /// Java caller of a native method.
+    pub fn rethrow(&self, env: &JNIEnv) -> Result<()> {
+        env.throw(JThrowable::from(self.throwable.as_obj()))
+    }
+}
+
+impl fmt::Display for JavaExceptionDetails {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.class)?;
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        for cause in &self.causes {
+            write!(f, ", caused by {}", cause.class)?;
+            if let Some(message) = &cause.message {
+                write!(f, ": {}", message)?;
+            }
+        }
+        Ok(())
+    }
 }
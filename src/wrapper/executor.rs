@@ -1,6 +1,11 @@
 use std::sync::Arc;
 
-use crate::{errors::*, objects::JObject, JNIEnv, JavaVM};
+use crate::{
+    errors::*,
+    objects::{GlobalRef, JObject},
+    wrapper::jnienv::with_thread_class_loader,
+    JNIEnv, JavaVM,
+};
 
 /// The capacity of local frames, allocated for attached threads by default. Same as the default
 /// value Hotspot uses when calling native Java methods.
@@ -52,12 +57,32 @@ pub const DEFAULT_LOCAL_FRAME_CAPACITY: i32 = 32;
 #[derive(Clone)]
 pub struct Executor {
     vm: Arc<JavaVM>,
+    class_loader: Option<GlobalRef>,
 }
 
 impl Executor {
     /// Creates new Executor with specified JVM.
     pub fn new(vm: Arc<JavaVM>) -> Self {
-        Self { vm }
+        Self {
+            vm,
+            class_loader: None,
+        }
+    }
+
+    /// Creates new Executor with specified JVM, which looks classes up with
+    /// the given class loader.
+    ///
+    /// Within the closures it executes, `JNIEnv::find_class`, and so all the
+    /// methods taking a class name, use `JNIEnv::find_class_with_loader` with
+    /// this class loader instead of the system class loader, so that the
+    /// classes of the application can be found from natively attached
+    /// threads. The class loader can be captured with e.g.
+    /// `JNIEnv::get_context_class_loader` in `JNI_OnLoad`.
+    pub fn new_with_class_loader(vm: Arc<JavaVM>, class_loader: GlobalRef) -> Self {
+        Self {
+            vm,
+            class_loader: Some(class_loader),
+        }
     }
 
     /// Executes a provided closure, making sure that the current thread
@@ -74,7 +99,10 @@ impl Executor {
         let jni_env = self.vm.attach_current_thread_as_daemon()?;
         let mut result = None;
         jni_env.with_local_frame(capacity, || {
-            result = Some(f(&jni_env));
+            result = Some(match &self.class_loader {
+                Some(class_loader) => with_thread_class_loader(class_loader, || f(&jni_env)),
+                None => f(&jni_env),
+            });
             Ok(JObject::null())
         })?;
 
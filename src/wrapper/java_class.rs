@@ -0,0 +1,509 @@
+//! Support code for the `java_class!` macro.
+
+use std::{os::raw::c_void, str::FromStr, sync::OnceLock};
+
+use crate::{
+    errors::*,
+    objects::{GlobalRef, JFieldID, JMethodID, JStaticFieldID, JStaticMethodID, JValue},
+    signature::{JavaType, Primitive, ReturnType, TypeSignature},
+    sys::{jfieldID, jmethodID},
+    JNIEnv,
+};
+
+/// Declares a typed binding for a Java class.
+///
+/// This generates a wrapper struct for objects of the class, with methods for
+/// the constructors, methods, static methods and fields that are declared
+/// along with their JNI signatures. Each call passes its arguments with their
+/// Rust types and returns the result with its Rust type, like the typed calls
+/// of `JNIEnv` (see `signature::MethodSignature`).
+///
+/// The class is looked up once, and kept alive with a `GlobalRef`, and the ID
+/// of each method and field is looked up the first time it is used and reused
+/// afterwards, by all the threads. The signature of each method or field is
+/// checked against the declared argument and return types, or field type, when
+/// its ID is looked up.
+///
+/// The wrapper is `Name<'a, 'b>`, which holds a `JObject<'a>` and the
+/// `&'b JNIEnv<'a>` it is used with, like `JList` and `JMap`. The `'a`
+/// lifetime can be used in the declared types, e.g. `JString<'a>`.
+///
+/// Arguments are converted with `JValue::from`, so they can be primitives
+/// (`jint`, `bool`, ...) or anything that converts into a `JObject`, and
+/// returned values and field values are unwrapped with `JavaReturn`, so they
+/// can be primitives, `()`, `JObject<'a>`, `JString<'a>`, `JClass<'a>` or
+/// `JThrowable<'a>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use jni::{java_class, objects::JString, sys::jint};
+///
+/// java_class! {
+///     /// A `java.util.concurrent.atomic.AtomicInteger`.
+///     pub class AtomicInteger = "java/util/concurrent/atomic/AtomicInteger" {
+///         pub constructor new(initial_value: jint) = "(I)V";
+///         pub fn get() -> jint = "get", "()I";
+///         pub fn add_and_get(delta: jint) -> jint = "addAndGet", "(I)I";
+///         pub fn set(value: jint) = "set", "(I)V";
+///         pub fn to_string() -> JString<'a> = "toString", "()Ljava/lang/String;";
+///         // `getter, setter: type`; the setter is optional.
+///         field value, set_value: jint = "value", "I";
+///     }
+/// }
+///
+/// java_class! {
+///     pub class Integer = "java/lang/Integer" {
+///         pub static fn parse_int(s: JString<'a>) -> jint = "parseInt", "(Ljava/lang/String;)I";
+///         pub static field max_value: jint = "MAX_VALUE", "I";
+///     }
+/// }
+///
+/// let counter = AtomicInteger::new(&env, 41)?;
+/// assert_eq!(counter.add_and_get(1)?, 42);
+/// assert_eq!(Integer::max_value(&env)?, i32::MAX);
+/// ```
+///
+/// The class is found with `JNIEnv::find_class` the first time it's needed, so
+/// it must be visible to the class loader used by `find_class` on that thread.
+#[macro_export]
+macro_rules! java_class {
+    (@items $name:ident) => {};
+
+    (@items $name:ident
+        $(#[$meta:meta])*
+        $vis:vis constructor $fn:ident($($arg:ident: $arg_ty:ty),* $(,)?) = $sig:literal;
+        $($rest:tt)*
+    ) => {
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            $(#[$meta])*
+            $vis fn $fn(
+                env: &'b $crate::JNIEnv<'a>,
+                $($arg: $arg_ty),*
+            ) -> $crate::errors::Result<Self> {
+                let args: $crate::java_class!(@args $($arg)*) = [$($crate::objects::JValue::from($arg)),*];
+                static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                let id = ID.constructor(env, Self::class(env)?, $sig, &args)?;
+                let obj = env.new_object_unchecked(Self::class(env)?, id, &args)?;
+                Ok(Self::from_env(env, obj))
+            }
+        }
+
+        $crate::java_class!(@items $name $($rest)*);
+    };
+
+    (@items $name:ident
+        $(#[$meta:meta])*
+        $vis:vis fn $fn:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)? = $method:literal, $sig:literal;
+        $($rest:tt)*
+    ) => {
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            $(#[$meta])*
+            $vis fn $fn(
+                &self,
+                $($arg: $arg_ty),*
+            ) -> $crate::errors::Result<$crate::java_class!(@output $($ret)?)> {
+                use $crate::signature::JavaReturn;
+
+                let args: $crate::java_class!(@args $($arg)*) = [$($crate::objects::JValue::from($arg)),*];
+                static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                let id = ID.method(
+                    self.env,
+                    Self::class(self.env)?,
+                    $method,
+                    $sig,
+                    &args,
+                    <$crate::java_class!(@ret $($ret)?)>::return_type(),
+                )?;
+                let ret = self.env.call_method_unchecked(
+                    self.internal,
+                    id,
+                    <$crate::java_class!(@ret $($ret)?)>::return_type(),
+                    &args.map($crate::objects::JValue::to_jni),
+                )?;
+                <$crate::java_class!(@ret $($ret)?)>::from_jvalue(ret)
+            }
+        }
+
+        $crate::java_class!(@items $name $($rest)*);
+    };
+
+    (@items $name:ident
+        $(#[$meta:meta])*
+        $vis:vis static fn $fn:ident($($arg:ident: $arg_ty:ty),* $(,)?) $(-> $ret:ty)? = $method:literal, $sig:literal;
+        $($rest:tt)*
+    ) => {
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            $(#[$meta])*
+            $vis fn $fn(
+                env: &$crate::JNIEnv<'a>,
+                $($arg: $arg_ty),*
+            ) -> $crate::errors::Result<$crate::java_class!(@output $($ret)?)> {
+                use $crate::signature::JavaReturn;
+
+                let args: $crate::java_class!(@args $($arg)*) = [$($crate::objects::JValue::from($arg)),*];
+                static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                let id = ID.static_method(
+                    env,
+                    Self::class(env)?,
+                    $method,
+                    $sig,
+                    &args,
+                    <$crate::java_class!(@ret $($ret)?)>::return_type(),
+                )?;
+                let ret = env.call_static_method_unchecked(
+                    Self::class(env)?,
+                    id,
+                    <$crate::java_class!(@ret $($ret)?)>::return_type(),
+                    &args.map($crate::objects::JValue::to_jni),
+                )?;
+                <$crate::java_class!(@ret $($ret)?)>::from_jvalue(ret)
+            }
+        }
+
+        $crate::java_class!(@items $name $($rest)*);
+    };
+
+    (@items $name:ident
+        $(#[$meta:meta])*
+        $vis:vis field $get:ident $(, $set:ident)?: $ty:ty = $field:literal, $sig:literal;
+        $($rest:tt)*
+    ) => {
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            $(#[$meta])*
+            $vis fn $get(&self) -> $crate::errors::Result<<$ty as $crate::signature::JavaReturn<'a>>::Output> {
+                use $crate::signature::JavaReturn;
+
+                static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                let id = ID.field(
+                    self.env,
+                    Self::class(self.env)?,
+                    $field,
+                    $sig,
+                    <$ty>::return_type(),
+                )?;
+                let value = self.env.get_field_unchecked(self.internal, id, <$ty>::return_type())?;
+                <$ty>::from_jvalue(value)
+            }
+
+            $(
+                #[doc = concat!("Sets the `", $field, "` field.")]
+                $vis fn $set(&self, value: $ty) -> $crate::errors::Result<()> {
+                    use $crate::signature::JavaReturn;
+
+                    static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                    let id = ID.field(
+                        self.env,
+                        Self::class(self.env)?,
+                        $field,
+                        $sig,
+                        <$ty>::return_type(),
+                    )?;
+                    self.env.set_field_unchecked(self.internal, id, $crate::objects::JValue::from(value))
+                }
+            )?
+        }
+
+        $crate::java_class!(@items $name $($rest)*);
+    };
+
+    (@items $name:ident
+        $(#[$meta:meta])*
+        $vis:vis static field $get:ident $(, $set:ident)?: $ty:ty = $field:literal, $sig:literal;
+        $($rest:tt)*
+    ) => {
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            $(#[$meta])*
+            $vis fn $get(env: &$crate::JNIEnv<'a>) -> $crate::errors::Result<<$ty as $crate::signature::JavaReturn<'a>>::Output> {
+                use $crate::signature::JavaReturn;
+
+                static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                let id = ID.static_field(
+                    env,
+                    Self::class(env)?,
+                    $field,
+                    $sig,
+                    <$ty>::return_type(),
+                )?;
+                let value = env.get_static_field_unchecked(
+                    Self::class(env)?,
+                    id,
+                    $crate::java_class::field_type(<$ty>::return_type()),
+                )?;
+                <$ty>::from_jvalue(value)
+            }
+
+            $(
+                #[doc = concat!("Sets the static `", $field, "` field.")]
+                $vis fn $set(env: &$crate::JNIEnv<'a>, value: $ty) -> $crate::errors::Result<()> {
+                    use $crate::signature::JavaReturn;
+
+                    static ID: $crate::java_class::LazyId = $crate::java_class::LazyId::new();
+                    let id = ID.static_field(
+                        env,
+                        Self::class(env)?,
+                        $field,
+                        $sig,
+                        <$ty>::return_type(),
+                    )?;
+                    env.set_static_field(Self::class(env)?, id, $crate::objects::JValue::from(value))
+                }
+            )?
+        }
+
+        $crate::java_class!(@items $name $($rest)*);
+    };
+
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+
+    (@output) => { () };
+    (@output $ret:ty) => { <$ret as $crate::signature::JavaReturn<'a>>::Output };
+
+    (@args $($arg:ident)*) => {
+        [$crate::objects::JValue<'_>; <[&str]>::len(&[$(stringify!($arg)),*])]
+    };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis class $name:ident = $class:literal {
+            $($items:tt)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy)]
+        $vis struct $name<'a: 'b, 'b> {
+            internal: $crate::objects::JObject<'a>,
+            env: &'b $crate::JNIEnv<'a>,
+        }
+
+        impl<'a: 'b, 'b> ::std::ops::Deref for $name<'a, 'b> {
+            type Target = $crate::objects::JObject<'a>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.internal
+            }
+        }
+
+        impl<'a: 'b, 'b> From<$name<'a, 'b>> for $crate::objects::JObject<'a> {
+            fn from(other: $name<'a, 'b>) -> $crate::objects::JObject<'a> {
+                other.internal
+            }
+        }
+
+        impl<'a: 'b, 'b> $name<'a, 'b> {
+            /// The name of the Java class.
+            pub const CLASS_NAME: &'static str = $class;
+
+            /// Wraps an object of the class. This doesn't check the class of
+            /// the object, but calling methods on an object of the wrong class
+            /// will fail.
+            pub fn from_env(env: &'b $crate::JNIEnv<'a>, obj: $crate::objects::JObject<'a>) -> Self {
+                $name { internal: obj, env }
+            }
+
+            /// Returns the Java class, which is looked up once.
+            pub fn class(env: &$crate::JNIEnv) -> $crate::errors::Result<&'static $crate::objects::GlobalRef> {
+                static CLASS: $crate::java_class::LazyClass = $crate::java_class::LazyClass::new();
+                CLASS.get(env, $class)
+            }
+        }
+
+        $crate::java_class!(@items $name $($items)*);
+    };
+}
+
+#[doc(hidden)]
+#[derive(Default)]
+pub struct LazyClass(OnceLock<GlobalRef>);
+
+impl LazyClass {
+    pub const fn new() -> Self {
+        LazyClass(OnceLock::new())
+    }
+
+    pub fn get(&self, env: &JNIEnv, name: &str) -> Result<&GlobalRef> {
+        if let Some(class) = self.0.get() {
+            return Ok(class);
+        }
+        let class = env.auto_local(env.find_class(name)?);
+        let class = env.new_global_ref(&class)?;
+        // Another thread may have been first, in which case this class is
+        // dropped.
+        Ok(self.0.get_or_init(|| class))
+    }
+}
+
+// A `jmethodID` or `jfieldID`, stored as an address as these are valid across
+// threads, as long as the class isn't unloaded, which `LazyClass` prevents.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct LazyId(OnceLock<usize>);
+
+impl LazyId {
+    pub const fn new() -> Self {
+        LazyId(OnceLock::new())
+    }
+
+    fn get_or_try_init<F>(&self, init: F) -> Result<*mut c_void>
+    where
+        F: FnOnce() -> Result<*mut c_void>,
+    {
+        if let Some(&id) = self.0.get() {
+            return Ok(id as *mut c_void);
+        }
+        let id = init()? as usize;
+        Ok(*self.0.get_or_init(|| id) as *mut c_void)
+    }
+
+    pub fn constructor(
+        &self,
+        env: &JNIEnv,
+        class: &GlobalRef,
+        sig: &str,
+        args: &[JValue],
+    ) -> Result<JMethodID> {
+        let void = ReturnType::Primitive(Primitive::Void);
+        let id = self.get_or_try_init(|| {
+            if TypeSignature::from_str(sig)?.ret != void {
+                return Err(Error::InvalidCtorReturn);
+            }
+            check_method_signature(sig, args, void.clone())?;
+            Ok(env.get_method_id(class, "<init>", sig)?.into_inner() as *mut c_void)
+        })?;
+        Ok(JMethodID::from(id as jmethodID))
+    }
+
+    pub fn method(
+        &self,
+        env: &JNIEnv,
+        class: &GlobalRef,
+        name: &str,
+        sig: &str,
+        args: &[JValue],
+        ret: ReturnType,
+    ) -> Result<JMethodID> {
+        let id = self.get_or_try_init(|| {
+            check_method_signature(sig, args, ret)?;
+            Ok(env.get_method_id(class, name, sig)?.into_inner() as *mut c_void)
+        })?;
+        Ok(JMethodID::from(id as jmethodID))
+    }
+
+    pub fn static_method(
+        &self,
+        env: &JNIEnv,
+        class: &GlobalRef,
+        name: &str,
+        sig: &str,
+        args: &[JValue],
+        ret: ReturnType,
+    ) -> Result<JStaticMethodID> {
+        let id = self.get_or_try_init(|| {
+            check_method_signature(sig, args, ret)?;
+            Ok(env.get_static_method_id(class, name, sig)?.into_inner() as *mut c_void)
+        })?;
+        Ok(JStaticMethodID::from(id as jmethodID))
+    }
+
+    pub fn field<'f>(
+        &self,
+        env: &JNIEnv,
+        class: &GlobalRef,
+        name: &str,
+        sig: &str,
+        ty: ReturnType,
+    ) -> Result<JFieldID<'f>> {
+        let id = self.get_or_try_init(|| {
+            check_field_signature(sig, ty)?;
+            Ok(env.get_field_id(class, name, sig)?.into_inner() as *mut c_void)
+        })?;
+        Ok(JFieldID::from(id as jfieldID))
+    }
+
+    pub fn static_field<'f>(
+        &self,
+        env: &JNIEnv,
+        class: &GlobalRef,
+        name: &str,
+        sig: &str,
+        ty: ReturnType,
+    ) -> Result<JStaticFieldID<'f>> {
+        let id = self.get_or_try_init(|| {
+            check_field_signature(sig, ty)?;
+            Ok(env.get_static_field_id(class, name, sig)?.into_inner() as *mut c_void)
+        })?;
+        Ok(JStaticFieldID::from(id as jfieldID))
+    }
+}
+
+/// The field type to pass to `JNIEnv::get_static_field_unchecked` for a
+/// declared field type.
+#[doc(hidden)]
+pub fn field_type(ty: ReturnType) -> JavaType {
+    match ty {
+        ReturnType::Primitive(p) => JavaType::Primitive(p),
+        // Only whether the type is an object or not matters.
+        ReturnType::Object | ReturnType::Array => JavaType::Object(String::new()),
+    }
+}
+
+// Checks a method signature against the declared arguments, whose types are
+// given by the `JValue`s they convert into, and return type.
+fn check_method_signature(sig: &str, args: &[JValue], ret: ReturnType) -> Result<()> {
+    let parsed = TypeSignature::from_str(sig)?;
+    if parsed.args.len() != args.len() {
+        return Err(Error::InvalidArgList(parsed));
+    }
+    for (ty, arg) in parsed.args.iter().zip(args) {
+        let declared = match arg.primitive_type() {
+            Some(p) => ReturnType::Primitive(p),
+            None => ReturnType::Object,
+        };
+        check_type(&value_type(ty)?, &declared)?;
+    }
+    check_type(&parsed.ret, &ret)
+}
+
+fn check_field_signature(sig: &str, ty: ReturnType) -> Result<()> {
+    check_type(&value_type(&JavaType::from_str(sig)?)?, &ty)
+}
+
+// The type of a value of a `JavaType`, which can't be a method.
+fn value_type(ty: &JavaType) -> Result<ReturnType> {
+    Ok(match ty {
+        JavaType::Primitive(p) => ReturnType::Primitive(*p),
+        JavaType::Object(_) => ReturnType::Object,
+        JavaType::Array(_) => ReturnType::Array,
+        JavaType::Method(_) => return Err(Error::WrongJValueType("value", "method")),
+    })
+}
+
+// Checks that a type from a signature matches the declared Rust type, which
+// maps arrays to objects.
+fn check_type(sig: &ReturnType, declared: &ReturnType) -> Result<()> {
+    match (sig, declared) {
+        (ReturnType::Primitive(a), ReturnType::Primitive(b)) if a == b => Ok(()),
+        (ReturnType::Object, ReturnType::Object) | (ReturnType::Array, ReturnType::Object) => {
+            Ok(())
+        }
+        _ => Err(Error::WrongJValueType(type_name(declared), type_name(sig))),
+    }
+}
+
+fn type_name(ty: &ReturnType) -> &'static str {
+    match ty {
+        ReturnType::Object | ReturnType::Array => "object",
+        ReturnType::Primitive(p) => match p {
+            Primitive::Boolean => "bool",
+            Primitive::Byte => "byte",
+            Primitive::Char => "char",
+            Primitive::Double => "double",
+            Primitive::Float => "float",
+            Primitive::Int => "int",
+            Primitive::Long => "long",
+            Primitive::Short => "short",
+            Primitive::Void => "void",
+        },
+    }
+}
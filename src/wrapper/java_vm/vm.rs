@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    ops::Deref,
+    ops::{Deref, DerefMut},
     ptr,
     sync::atomic::{AtomicUsize, Ordering},
     thread::current,
@@ -332,6 +332,12 @@ impl<'a> Deref for AttachGuard<'a> {
     }
 }
 
+impl<'a> DerefMut for AttachGuard<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.env
+    }
+}
+
 impl<'a> Drop for AttachGuard<'a> {
     fn drop(&mut self) {
         if self.should_detach {
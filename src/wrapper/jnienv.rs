@@ -1,32 +1,68 @@
 use std::{
+    cell::{Cell, RefCell},
     marker::PhantomData,
     os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
     ptr, slice, str,
     str::FromStr,
     sync::{Mutex, MutexGuard},
 };
 
-use log::warn;
+use log::{error, warn};
 
 use crate::signature::ReturnType;
 use crate::{
+    convert::IntoJavaArgs,
     descriptors::Desc,
     errors::*,
+    native::{panic_message, NativeReturn},
     objects::{
-        AutoArray, AutoLocal, AutoPrimitiveArray, GlobalRef, JByteBuffer, JClass, JFieldID, JList,
-        JMap, JMethodID, JObject, JStaticFieldID, JStaticMethodID, JString, JThrowable, JValue,
-        ReleaseMode, TypeArray,
+        ArrayElement, AutoArray, AutoLocal, AutoPrimitiveArray, Boxable, GlobalRef, JBooleanArray,
+        JByteArray, JByteBuffer, JByteBufferStream, JCharArray, JClass, JCollection, JDoubleArray,
+        JFieldID, JFloatArray, JIntArray, JIterable, JIterator, JList, JLongArray, JMap, JMethodID,
+        JObject, JObjectArray, JObjectArrayIter, JObjectRefType, JPrimitiveArray, JSet,
+        JShortArray, JStaticFieldID, JStaticMethodID, JString, JThrowable, JValue, ReleaseMode,
+        TypeArray, WeakRef,
     },
-    signature::{JavaType, Primitive, TypeSignature},
-    strings::{JNIString, JavaStr},
+    signature::{JavaArgs, JavaReturn, JavaType, MethodSignature, Primitive, TypeSignature},
+    strings::{JNIString, JavaChars, JavaCharsCritical, JavaStr},
     sys::{
-        self, jarray, jboolean, jbooleanArray, jbyte, jbyteArray, jchar, jcharArray, jdouble,
-        jdoubleArray, jfloat, jfloatArray, jint, jintArray, jlong, jlongArray, jobjectArray,
-        jshort, jshortArray, jsize, jvalue, JNINativeMethod,
+        self, jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort, jsize, jvalue,
+        JNINativeMethod,
     },
     JNIVersion, JavaVM,
 };
 
+thread_local! {
+    // The class loader used by `find_class` on this thread, if any, see
+    // `with_thread_class_loader`.
+    static THREAD_CLASS_LOADER: RefCell<Option<GlobalRef>> = const { RefCell::new(None) };
+}
+
+// Runs `f` with `find_class` using `loader` on the current thread.
+pub(crate) fn with_thread_class_loader<F, R>(loader: &GlobalRef, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct RestoreGuard(Option<GlobalRef>);
+
+    impl Drop for RestoreGuard {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            THREAD_CLASS_LOADER.with(|loader| *loader.borrow_mut() = previous);
+        }
+    }
+
+    let previous = THREAD_CLASS_LOADER.with(|current| current.replace(Some(loader.clone())));
+    let _guard = RestoreGuard(previous);
+    f()
+}
+
+// The class loader used by `find_class` on the current thread, if any.
+pub(crate) fn thread_class_loader() -> Option<GlobalRef> {
+    THREAD_CLASS_LOADER.with(|loader| loader.borrow().clone())
+}
+
 /// FFI-compatible JNIEnv struct. You can safely use this as the JNIEnv argument
 /// to exported methods that will be called by java. This is where most of the
 /// magic happens. All methods on this object are wrappers around JNI functions,
@@ -139,6 +175,15 @@ impl<'a> JNIEnv<'a> {
 
     /// Look up a class by name.
     ///
+    /// `FindClass` uses the class loader of the native method being called,
+    /// or the system class loader when there is none, as on a thread that was
+    /// attached natively. So on such threads, the classes of an application
+    /// using its own class loaders (e.g. on Android, or in an application
+    /// server) are not found. Use `find_class_with_loader` to look classes up
+    /// with a specific class loader instead, or an `Executor` created with
+    /// `Executor::new_with_class_loader`, which makes this function use the
+    /// class loader within its closures.
+    ///
     /// # Example
     /// ```rust,ignore
     /// let class: JClass<'a> = env.find_class("java/lang/String");
@@ -147,11 +192,112 @@ impl<'a> JNIEnv<'a> {
     where
         S: Into<JNIString>,
     {
-        let name = name.into();
+        if let Some(loader) = thread_class_loader() {
+            return self.find_class_with_loader(name, loader.as_obj());
+        }
+        self.find_class_raw(name.into())
+    }
+
+    fn find_class_raw(&self, name: JNIString) -> Result<JClass<'a>> {
         let class = jni_non_null_call!(self.internal, FindClass, name.as_ptr());
         Ok(class)
     }
 
+    /// Look up a class by name with the given class loader, using
+    /// `ClassLoader#loadClass`.
+    ///
+    /// The name can be given in the same form as for `find_class`, e.g.
+    /// `java/lang/String` or `[Ljava/lang/String;`, or as a binary name, e.g.
+    /// `java.lang.String`. Array classes and a null `loader`, which stands
+    /// for the bootstrap class loader, are looked up with `Class#forName`
+    /// instead, without initializing the class.
+    ///
+    /// Unlike `find_class`, a `java.lang.ClassNotFoundException` is thrown if
+    /// the class can't be found.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // Captured when the library is loaded, e.g. in `JNI_OnLoad`.
+    /// let loader = env.new_global_ref(env.get_context_class_loader()?)?;
+    ///
+    /// // Later, on a natively attached thread:
+    /// let class = env.find_class_with_loader("com/example/Foo", loader.as_obj())?;
+    /// ```
+    pub fn find_class_with_loader<'l, S, L>(&self, name: S, loader: L) -> Result<JClass<'a>>
+    where
+        S: Into<JNIString>,
+        L: Into<JObject<'l>>,
+    {
+        let loader = loader.into();
+        let name: String = name.into().into();
+        let binary_name = self.auto_local(self.new_string(name.replace('/', "."))?);
+
+        let class = if loader.is_null() || name.starts_with('[') {
+            // Looked up directly, as this may be called by `find_class`.
+            let class_class = self.auto_local(self.find_class_raw("java/lang/Class".into())?);
+            self.call_static_method(
+                &class_class,
+                "forName",
+                "(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+                &[
+                    JValue::from(&binary_name),
+                    JValue::from(false),
+                    JValue::from(loader),
+                ],
+            )?
+        } else {
+            self.call_method(
+                loader.into_inner(),
+                "loadClass",
+                "(Ljava/lang/String;)Ljava/lang/Class;",
+                &[JValue::from(&binary_name)],
+            )?
+        };
+        Ok(class.l()?.into())
+    }
+
+    /// Returns the class loader that defined a class, or a null object for
+    /// the bootstrap class loader. This can be captured (with a `GlobalRef`)
+    /// to find the classes of an application from natively attached threads
+    /// later, see `find_class_with_loader`.
+    pub fn get_class_loader<'c, T>(&self, class: T) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+    {
+        let class = class.lookup(self)?;
+        self.call_method(
+            class.into_inner(),
+            "getClassLoader",
+            "()Ljava/lang/ClassLoader;",
+            &[],
+        )?
+        .l()
+    }
+
+    /// Returns the context class loader of the current thread, which is
+    /// usually the application class loader in `JNI_OnLoad` and on threads
+    /// started by the application, and can be captured for natively attached
+    /// threads, see `find_class_with_loader`. Natively attached threads
+    /// typically don't have one, in which case a null object is returned.
+    pub fn get_context_class_loader(&self) -> Result<JObject<'a>> {
+        let thread = self.auto_local(
+            self.call_static_method(
+                "java/lang/Thread",
+                "currentThread",
+                "()Ljava/lang/Thread;",
+                &[],
+            )?
+            .l()?,
+        );
+        self.call_method(
+            thread.as_obj().into_inner(),
+            "getContextClassLoader",
+            "()Ljava/lang/ClassLoader;",
+            &[],
+        )?
+        .l()
+    }
+
     /// Returns the superclass for a particular class OR `JObject::null()` for `java.lang.Object` or
     /// an interface. As with `find_class`, takes a descriptor.
     pub fn get_superclass<'c, T>(&self, class: T) -> Result<JClass<'a>>
@@ -162,6 +308,28 @@ impl<'a> JNIEnv<'a> {
         Ok(jni_non_void_call!(self.internal, GetSuperclass, class.into_inner()).into())
     }
 
+    /// Returns the `java.lang.Module` that a class is a member of. As with
+    /// `find_class`, takes a descriptor.
+    ///
+    /// `GetModule` was added in JNI 9: if the JVM reports an older version,
+    /// `Error::JniCall(JniError::WrongVersion)` is returned.
+    pub fn get_module<'c, T>(&self, class: T) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+    {
+        // Older function tables don't have this entry at all, so it must not
+        // be looked up before checking the version.
+        if self.get_version()? < JNIVersion::V9 {
+            return Err(Error::JniCall(JniError::WrongVersion));
+        }
+        let class = class.lookup(self)?;
+        Ok(jni_non_null_call!(
+            self.internal,
+            GetModule,
+            class.into_inner()
+        ))
+    }
+
     /// Tests whether class1 is assignable from class2.
     pub fn is_assignable_from<'t, 'u, T, U>(&self, class1: T, class2: U) -> Result<bool>
     where
@@ -262,6 +430,72 @@ impl<'a> JNIEnv<'a> {
         }
     }
 
+    /// Runs the body of a native method, turning errors and panics into Java
+    /// exceptions instead of letting them unwind into the JVM.
+    ///
+    /// The closure may return any [`NativeReturn`](crate::native::NativeReturn)
+    /// type, including a `Result` whose error type implements `ToException`
+    /// (such as `jni::errors::Error`). The raw value to return from the
+    /// native method is returned.
+    ///
+    /// * On `Err`, the error is thrown with `ToException::throw_exception`,
+    ///   unless an exception is already pending (as is the case for
+    ///   `Error::JavaException`), which is left as is. By default this throws
+    ///   a new exception with `throw_new`, while `Error::CaughtJavaException`
+    ///   rethrows the original exception.
+    /// * On panic, a `java.lang.RuntimeException` with the panic message is
+    ///   thrown, unless an exception is already pending.
+    ///
+    /// In both cases, the default value of the return type (`0`, `false` or
+    /// `null`) is returned, which the JVM ignores because of the pending
+    /// exception.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// #[no_mangle]
+    /// pub extern "system" fn Java_HelloWorld_hello(
+    ///     env: JNIEnv,
+    ///     _class: JClass,
+    ///     input: JString,
+    /// ) -> jstring {
+    ///     env.catch_and_throw(|| {
+    ///         let input: String = env.get_string(input)?.into();
+    ///         env.new_string(format!("Hello, {}!", input))
+    ///     })
+    /// }
+    /// ```
+    pub fn catch_and_throw<R, F>(&self, f: F) -> R::Abi
+    where
+        R: NativeReturn,
+        F: FnOnce() -> R,
+    {
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(ret) => ret.into_abi(self),
+            Err(payload) => {
+                self.throw_unless_pending(&Exception {
+                    class: "java/lang/RuntimeException".to_string(),
+                    msg: panic_message(&*payload).to_string(),
+                });
+                R::abi_default()
+            }
+        }
+    }
+
+    // Throws `exception`, unless an exception is already pending. Failures
+    // can only be logged, as this is used when a native method is about to
+    // return to the JVM.
+    pub(crate) fn throw_unless_pending<E: ToException>(&self, exception: &E) {
+        match self.exception_check() {
+            Ok(false) => {
+                if let Err(e) = exception.throw_exception(self) {
+                    error!("failed to throw exception from native method: {}", e);
+                }
+            }
+            Ok(true) => {}
+            Err(e) => error!("failed to check for a pending exception: {}", e),
+        }
+    }
+
     /// Check whether or not an exception is currently in the process of being
     /// thrown. An exception is in this state from the time it gets thrown and
     /// not caught in a java function until `exception_clear` is called.
@@ -284,6 +518,142 @@ impl<'a> JNIEnv<'a> {
         Ok(())
     }
 
+    // Returns the error for an exception that is pending after a JNI call.
+    //
+    // This is `Error::JavaException`, leaving the exception pending, unless
+    // exception capture is enabled: then the exception is cleared and its
+    // details are returned in `Error::CaughtJavaException`. Capturing makes
+    // JNI calls of its own, which end up here again if they throw, so it is
+    // guarded against reentrancy.
+    pub(crate) fn pending_exception_error(&self) -> Error {
+        thread_local! {
+            static CAPTURING: Cell<bool> = const { Cell::new(false) };
+        }
+
+        struct CaptureGuard;
+
+        impl Drop for CaptureGuard {
+            fn drop(&mut self) {
+                CAPTURING.with(|capturing| capturing.set(false));
+            }
+        }
+
+        if !is_exception_capture_enabled() || CAPTURING.with(|capturing| capturing.replace(true)) {
+            return Error::JavaException;
+        }
+        let _guard = CaptureGuard;
+
+        self.capture_pending_exception()
+            .unwrap_or(Error::JavaException)
+    }
+
+    fn capture_pending_exception(&self) -> Result<Error> {
+        let throwable = jni_unchecked!(self.internal, ExceptionOccurred);
+        if throwable.is_null() {
+            return Ok(Error::JavaException);
+        }
+        let throwable = JThrowable::from(throwable);
+        jni_unchecked!(self.internal, ExceptionClear);
+
+        // Describing the throwable creates a few local references, which are
+        // all freed with the frame.
+        let details = if self.push_local_frame(16).is_ok() {
+            let details = self.describe_throwable(throwable);
+            jni_unchecked!(self.internal, PopLocalFrame, ptr::null_mut());
+            details
+        } else {
+            Err(Error::JavaException)
+        };
+
+        match details {
+            Ok(details) => {
+                jni_unchecked!(self.internal, DeleteLocalRef, throwable.into_inner());
+                Ok(Error::CaughtJavaException(Box::new(details)))
+            }
+            Err(e) => {
+                warn!("failed to capture java exception details: {}", e);
+                // Leave the original exception pending, as if capture was disabled.
+                jni_unchecked!(self.internal, ExceptionClear);
+                jni_unchecked!(self.internal, Throw, throwable.into_inner());
+                Ok(Error::JavaException)
+            }
+        }
+    }
+
+    // Collects the details of a throwable for `Error::CaughtJavaException`.
+    fn describe_throwable(&self, throwable: JThrowable) -> Result<JavaExceptionDetails> {
+        // Guards against cycles in the cause chain.
+        const MAX_CAUSES: usize = 32;
+
+        let (class, message) = self.throwable_class_and_message(throwable)?;
+
+        let mut causes = Vec::new();
+        let mut current = *throwable;
+        while causes.len() < MAX_CAUSES {
+            let cause = self
+                .call_method(current, "getCause", "()Ljava/lang/Throwable;", &[])?
+                .l()?;
+            if cause.is_null() || self.is_same_object(cause, current)? {
+                break;
+            }
+            let (class, message) = self.throwable_class_and_message(cause.into())?;
+            causes.push(JavaExceptionCause { class, message });
+            if current.into_inner() != throwable.into_inner() {
+                self.delete_local_ref(current)?;
+            }
+            current = cause;
+        }
+
+        let writer = self.new_object("java/io/StringWriter", "()V", &[])?;
+        let print_writer = self.new_object(
+            "java/io/PrintWriter",
+            "(Ljava/io/Writer;)V",
+            &[JValue::from(writer)],
+        )?;
+        self.call_method(
+            throwable,
+            "printStackTrace",
+            "(Ljava/io/PrintWriter;)V",
+            &[JValue::from(print_writer)],
+        )?;
+        let stack_trace = self
+            .call_method(writer, "toString", "()Ljava/lang/String;", &[])?
+            .l()?;
+        let stack_trace = self.get_string(stack_trace.into())?.into();
+
+        Ok(JavaExceptionDetails {
+            throwable: self.new_global_ref(throwable)?,
+            class,
+            message,
+            causes,
+            stack_trace,
+        })
+    }
+
+    fn throwable_class_and_message(
+        &self,
+        throwable: JThrowable,
+    ) -> Result<(String, Option<String>)> {
+        let class = self.auto_local(self.get_object_class(throwable)?);
+        let class = self.auto_local(
+            self.call_method(&class, "getName", "()Ljava/lang/String;", &[])?
+                .l()?,
+        );
+        let class = self.get_string(class.as_obj().into())?.into();
+
+        let message = self.auto_local(
+            self.call_method(throwable, "getMessage", "()Ljava/lang/String;", &[])?
+                .l()?,
+        );
+        let message = if message.as_obj().is_null() {
+            None
+        } else {
+            Some(self.get_string(message.as_obj().into())?.into())
+        };
+
+        Ok((class, message))
+    }
+
     /// Abort the JVM with an error message.
     #[allow(unused_variables, unreachable_code)]
     pub fn fatal_error<S: Into<JNIString>>(&self, msg: S) -> ! {
@@ -318,12 +688,19 @@ impl<'a> JNIEnv<'a> {
     /// Returns the starting address of the memory of the direct
     /// java.nio.ByteBuffer.
     pub fn get_direct_buffer_address(&self, buf: JByteBuffer) -> Result<&mut [u8]> {
+        let (ptr, capacity) = self.get_direct_buffer_memory(buf)?;
+        unsafe { Ok(slice::from_raw_parts_mut(ptr, capacity)) }
+    }
+
+    // The address and capacity of the memory of a direct buffer, for callers
+    // that don't hand out a slice of it.
+    pub(crate) fn get_direct_buffer_memory(&self, buf: JByteBuffer) -> Result<(*mut u8, usize)> {
         non_null!(buf, "get_direct_buffer_address argument");
         let ptr: *mut c_void =
             jni_unchecked!(self.internal, GetDirectBufferAddress, buf.into_inner());
         non_null!(ptr, "get_direct_buffer_address return value");
         let capacity = self.get_direct_buffer_capacity(buf)?;
-        unsafe { Ok(slice::from_raw_parts_mut(ptr as *mut u8, capacity as usize)) }
+        Ok((ptr as *mut u8, capacity as usize))
     }
 
     /// Returns the capacity of the direct java.nio.ByteBuffer.
@@ -335,6 +712,16 @@ impl<'a> JNIEnv<'a> {
         }
     }
 
+    /// Get a `JByteBufferStream` to read and write the bytes of a
+    /// `java.nio.ByteBuffer`, direct or not, through slices or the
+    /// `std::io` traits.
+    pub fn get_byte_buffer_stream(
+        &self,
+        buf: JByteBuffer<'a>,
+    ) -> Result<JByteBufferStream<'a, '_>> {
+        JByteBufferStream::from_env(self, buf)
+    }
+
     /// Turns an object into a global ref. This has the benefit of removing the
     /// lifetime bounds since it's guaranteed to not get GC'd by java. It
     /// releases the GC pin upon being dropped.
@@ -348,6 +735,40 @@ impl<'a> JNIEnv<'a> {
         Ok(global)
     }
 
+    /// Creates a new weak global reference to an object. Unlike `GlobalRef`, a
+    /// [`WeakRef`](objects/struct.WeakRef.html) doesn't prevent the object from being
+    /// garbage collected.
+    ///
+    /// If the provided object is null, this method returns `None`. Otherwise, it returns `Some`
+    /// containing the new weak global reference.
+    pub fn new_weak_ref<O>(&self, obj: O) -> Result<Option<WeakRef>>
+    where
+        O: Into<JObject<'a>>,
+    {
+        // Look up the VM first so that a failure here can't leak the new weak ref.
+        let vm = self.get_java_vm()?;
+
+        let obj = obj.into();
+
+        // Some JVMs throw an `OutOfMemoryError` from `NewWeakGlobalRef` when given `null`,
+        // contrary to the spec, so don't pass it on.
+        if obj.is_null() {
+            return Ok(None);
+        }
+
+        let weak: sys::jweak =
+            jni_non_void_call!(self.internal, NewWeakGlobalRef, obj.into_inner());
+
+        // `obj` may itself be a weak reference to an already collected object.
+        if weak.is_null() {
+            return Ok(None);
+        }
+
+        let weak = unsafe { WeakRef::from_raw(vm, weak) };
+
+        Ok(Some(weak))
+    }
+
     /// Create a new local ref to an object.
     ///
     /// Note that the object passed to this is *already* a local ref. This
@@ -389,11 +810,58 @@ impl<'a> JNIEnv<'a> {
     ///
     /// In most cases it is better to use `AutoLocal` (see `auto_local` method)
     /// or `with_local_frame` instead of direct `delete_local_ref` calls.
+    ///
+    /// In debug builds, this panics if `obj` is not a local reference
+    /// (e.g. it was already deleted, or is a global reference).
     pub fn delete_local_ref(&self, obj: JObject) -> Result<()> {
+        self.debug_assert_ref_type(obj, JObjectRefType::Local, "delete_local_ref");
         jni_unchecked!(self.internal, DeleteLocalRef, obj.into_inner());
         Ok(())
     }
 
+    /// Returns the kind of the given reference: local, global or weak global.
+    ///
+    /// `null` and references that have already been deleted are reported as
+    /// `JObjectRefType::Invalid`, though a JVM is not required to detect every
+    /// invalid reference.
+    pub fn get_object_ref_type<'b, O>(&self, obj: O) -> Result<JObjectRefType>
+    where
+        O: Into<JObject<'b>>,
+    {
+        // This method doesn't throw exceptions (see the spec)
+        let ref_type = jni_unchecked!(self.internal, GetObjectRefType, obj.into().into_inner());
+        Ok(ref_type.into())
+    }
+
+    // In debug builds, panics if `obj` is a non-null reference that is not of
+    // the `expected` kind. Used to catch double-deletes and references deleted
+    // with the wrong function, which otherwise crash the JVM without any
+    // diagnostics.
+    #[inline]
+    pub(crate) fn debug_assert_ref_type(
+        &self,
+        obj: JObject,
+        expected: JObjectRefType,
+        ctx: &'static str,
+    ) {
+        if !cfg!(debug_assertions) || obj.is_null() || ::std::thread::panicking() {
+            return;
+        }
+        // `GetObjectRefType` must not be called with a pending exception.
+        if self.exception_check().unwrap_or(true) {
+            return;
+        }
+        if let Ok(actual) = self.get_object_ref_type(obj) {
+            assert!(
+                actual == expected,
+                "{}: expected a {:?} reference but got {:?} (deleted twice, or with the wrong function?)",
+                ctx,
+                expected,
+                actual
+            );
+        }
+    }
+
     /// Creates a new local reference frame, in which at least a given number
     /// of local references can be created.
     ///
@@ -629,6 +1097,185 @@ impl<'a> JNIEnv<'a> {
         }
     }
 
+    /// Get the method ID of a `java.lang.reflect.Method` or
+    /// `java.lang.reflect.Constructor` object.
+    ///
+    /// The method must not be static, use `from_reflected_static_method`
+    /// for static methods. An `Error::WrongObjectType` is returned otherwise.
+    pub fn from_reflected_method<'b, O>(&self, method: O) -> Result<JMethodID>
+    where
+        O: Into<JObject<'b>>,
+    {
+        let method = method.into();
+        non_null!(method, "from_reflected_method method argument");
+        if self.is_static_member(method)? {
+            return Err(Error::WrongObjectType(
+                "non-static java.lang.reflect.Method",
+            ));
+        }
+        Ok(jni_non_null_call!(
+            self.internal,
+            FromReflectedMethod,
+            method.into_inner()
+        ))
+    }
+
+    /// Get the method ID of a static `java.lang.reflect.Method` object.
+    ///
+    /// The method must be static, use `from_reflected_method` for other
+    /// methods. An `Error::WrongObjectType` is returned otherwise.
+    pub fn from_reflected_static_method<'b, O>(&self, method: O) -> Result<JStaticMethodID>
+    where
+        O: Into<JObject<'b>>,
+    {
+        let method = method.into();
+        non_null!(method, "from_reflected_static_method method argument");
+        if !self.is_static_member(method)? {
+            return Err(Error::WrongObjectType("static java.lang.reflect.Method"));
+        }
+        Ok(jni_non_null_call!(
+            self.internal,
+            FromReflectedMethod,
+            method.into_inner()
+        ))
+    }
+
+    /// Get the field ID of a `java.lang.reflect.Field` object.
+    ///
+    /// The field must not be static, use `from_reflected_static_field` for
+    /// static fields. An `Error::WrongObjectType` is returned otherwise.
+    pub fn from_reflected_field<'b, O>(&self, field: O) -> Result<JFieldID<'a>>
+    where
+        O: Into<JObject<'b>>,
+    {
+        let field = field.into();
+        non_null!(field, "from_reflected_field field argument");
+        if self.is_static_member(field)? {
+            return Err(Error::WrongObjectType("non-static java.lang.reflect.Field"));
+        }
+        Ok(jni_non_null_call!(
+            self.internal,
+            FromReflectedField,
+            field.into_inner()
+        ))
+    }
+
+    /// Get the field ID of a static `java.lang.reflect.Field` object.
+    ///
+    /// The field must be static, use `from_reflected_field` for other fields.
+    /// An `Error::WrongObjectType` is returned otherwise.
+    pub fn from_reflected_static_field<'b, O>(&self, field: O) -> Result<JStaticFieldID<'a>>
+    where
+        O: Into<JObject<'b>>,
+    {
+        let field = field.into();
+        non_null!(field, "from_reflected_static_field field argument");
+        if !self.is_static_member(field)? {
+            return Err(Error::WrongObjectType("static java.lang.reflect.Field"));
+        }
+        Ok(jni_non_null_call!(
+            self.internal,
+            FromReflectedField,
+            field.into_inner()
+        ))
+    }
+
+    // Whether a `java.lang.reflect.Member` is static, as the IDs of static
+    // and non-static members can't be used in place of each other.
+    fn is_static_member(&self, member: JObject) -> Result<bool> {
+        // `java.lang.reflect.Modifier.STATIC`
+        const STATIC: jint = 0x0008;
+        let modifiers = self.call_method(member, "getModifiers", "()I", &[])?.i()?;
+        Ok(modifiers & STATIC != 0)
+    }
+
+    /// Get a `java.lang.reflect.Method` (or `java.lang.reflect.Constructor`)
+    /// object for a method of the given class.
+    ///
+    /// This function returns a local reference, that must not be allocated
+    /// excessively.
+    pub fn to_reflected_method<'c, T, U>(&self, class: T, method_id: U) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Desc<'a, JMethodID>,
+    {
+        let class = class.lookup(self)?;
+        let method_id = method_id.lookup(self)?.into_inner();
+        Ok(jni_non_null_call!(
+            self.internal,
+            ToReflectedMethod,
+            class.into_inner(),
+            method_id,
+            sys::JNI_FALSE
+        ))
+    }
+
+    /// Get a `java.lang.reflect.Method` object for a static method of the
+    /// given class.
+    ///
+    /// This function returns a local reference, that must not be allocated
+    /// excessively.
+    pub fn to_reflected_static_method<'c, T, U>(
+        &self,
+        class: T,
+        method_id: U,
+    ) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Desc<'a, JStaticMethodID>,
+    {
+        let class = class.lookup(self)?;
+        let method_id = method_id.lookup(self)?.into_inner();
+        Ok(jni_non_null_call!(
+            self.internal,
+            ToReflectedMethod,
+            class.into_inner(),
+            method_id,
+            sys::JNI_TRUE
+        ))
+    }
+
+    /// Get a `java.lang.reflect.Field` object for a field of the given class.
+    ///
+    /// This function returns a local reference, that must not be allocated
+    /// excessively.
+    pub fn to_reflected_field<'c, T, U>(&self, class: T, field_id: U) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Desc<'a, JFieldID<'a>>,
+    {
+        let class = class.lookup(self)?;
+        let field_id = field_id.lookup(self)?.into_inner();
+        Ok(jni_non_null_call!(
+            self.internal,
+            ToReflectedField,
+            class.into_inner(),
+            field_id,
+            sys::JNI_FALSE
+        ))
+    }
+
+    /// Get a `java.lang.reflect.Field` object for a static field of the given
+    /// class.
+    ///
+    /// This function returns a local reference, that must not be allocated
+    /// excessively.
+    pub fn to_reflected_static_field<'c, T, U>(&self, class: T, field_id: U) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Desc<'a, JStaticFieldID<'a>>,
+    {
+        let class = class.lookup(self)?;
+        let field_id = field_id.lookup(self)?.into_inner();
+        Ok(jni_non_null_call!(
+            self.internal,
+            ToReflectedField,
+            class.into_inner(),
+            field_id,
+            sys::JNI_TRUE
+        ))
+    }
+
     /// Get the class for an object.
     pub fn get_object_class<'b, O>(&self, obj: O) -> Result<JClass<'a>>
     where
@@ -827,22 +1474,158 @@ impl<'a> JNIEnv<'a> {
         }) // match parsed.ret
     }
 
-    /// Calls an object method safely. This comes with a number of
-    /// lookups/checks. It
+    /// Call a non-virtual object method in an unsafe manner. This does nothing to
+    /// check whether the method is valid to call on the object, whether the return
+    /// type is correct, or whether the number of args is valid for the method.
     ///
-    /// * Parses the type signature to find the number of arguments and return
-    ///   type
-    /// * Looks up the JClass for the given object.
-    /// * Looks up the JMethodID for the class/name/signature combination
-    /// * Ensures that the number of args matches the signature
-    /// * Calls `call_method_unchecked` with the verified safe arguments.
+    /// Unlike `call_method_unchecked`, the method that gets called is the one
+    /// defined by `class` (e.g. a superclass implementation), not the one resolved
+    /// from the runtime class of `obj`. This is how `super.foo()` calls can be made
+    /// from native code.
     ///
-    /// Note: this may cause a java exception if the arguments are the wrong
-    /// type, in addition to if the method itself throws.
-    pub fn call_method<O, S, T>(
+    /// Under the hood, this simply calls the `CallNonvirtual<Type>MethodA` method
+    /// with the provided arguments.
+    pub fn call_nonvirtual_method_unchecked<'c, O, T, U>(
         &self,
         obj: O,
-        name: S,
+        class: T,
+        method_id: U,
+        ret: ReturnType,
+        args: &[jvalue],
+    ) -> Result<JValue<'a>>
+    where
+        O: Into<JObject<'a>>,
+        T: Desc<'a, JClass<'c>>,
+        U: Desc<'a, JMethodID>,
+    {
+        let obj = obj.into();
+        non_null!(obj, "call_nonvirtual_method_unchecked obj argument");
+
+        let class = class.lookup(self)?;
+
+        let method_id = method_id.lookup(self)?.into_inner();
+
+        let obj = obj.into_inner();
+        let class = class.into_inner();
+        let jni_args = args.as_ptr();
+
+        Ok(match ret {
+            ReturnType::Object | ReturnType::Array => {
+                let obj: JObject = jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualObjectMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into();
+                obj.into()
+            }
+            ReturnType::Primitive(p) => match p {
+                Primitive::Boolean => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualBooleanMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Char => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualCharMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Short => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualShortMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Int => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualIntMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Long => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualLongMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Float => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualFloatMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Double => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualDoubleMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Byte => jni_non_void_call!(
+                    self.internal,
+                    CallNonvirtualByteMethodA,
+                    obj,
+                    class,
+                    method_id,
+                    jni_args
+                )
+                .into(),
+                Primitive::Void => {
+                    jni_void_call!(
+                        self.internal,
+                        CallNonvirtualVoidMethodA,
+                        obj,
+                        class,
+                        method_id,
+                        jni_args
+                    );
+                    return Ok(JValue::Void);
+                }
+            }, // JavaType::Primitive
+        }) // match parsed.ret
+    }
+
+    /// Calls an object method safely. This comes with a number of
+    /// lookups/checks. It
+    ///
+    /// * Parses the type signature to find the number of arguments and return
+    ///   type
+    /// * Looks up the JClass for the given object.
+    /// * Looks up the JMethodID for the class/name/signature combination
+    /// * Ensures that the number of args matches the signature
+    /// * Calls `call_method_unchecked` with the verified safe arguments.
+    ///
+    /// Note: this may cause a java exception if the arguments are the wrong
+    /// type, in addition to if the method itself throws.
+    pub fn call_method<O, S, T>(
+        &self,
+        obj: O,
+        name: S,
         sig: T,
         args: &[JValue],
     ) -> Result<JValue<'a>>
@@ -902,6 +1685,190 @@ impl<'a> JNIEnv<'a> {
         self.call_static_method_unchecked(class, (class, name, sig), parsed.ret, &args)
     }
 
+    /// Calls a non-virtual object method safely, i.e. the implementation defined
+    /// by `class` rather than the one that would be dispatched to based on the
+    /// runtime class of `obj`. This comes with a number of lookups/checks. It
+    ///
+    /// * Parses the type signature to find the number of arguments and return
+    ///   type
+    /// * Looks up the JMethodID for the class/name/signature combination
+    /// * Ensures that the number of args matches the signature
+    /// * Calls `call_nonvirtual_method_unchecked` with the verified safe arguments.
+    ///
+    /// Note: this may cause a java exception if the arguments are the wrong
+    /// type, in addition to if the method itself throws.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// // Calls `Object#toString` even if `obj` overrides it.
+    /// let s = env.call_nonvirtual_method(obj, "java/lang/Object", "toString", "()Ljava/lang/String;", &[])?;
+    /// ```
+    pub fn call_nonvirtual_method<'c, O, T, U, V>(
+        &self,
+        obj: O,
+        class: T,
+        name: U,
+        sig: V,
+        args: &[JValue],
+    ) -> Result<JValue<'a>>
+    where
+        O: Into<JObject<'a>>,
+        T: Desc<'a, JClass<'c>>,
+        U: Into<JNIString>,
+        V: Into<JNIString> + AsRef<str>,
+    {
+        let obj = obj.into();
+        non_null!(obj, "call_nonvirtual_method obj argument");
+
+        let parsed = TypeSignature::from_str(&sig)?;
+        if parsed.args.len() != args.len() {
+            return Err(Error::InvalidArgList(parsed));
+        }
+
+        // go ahead and look up the class since it's already Copy,
+        // and we'll need that for the next call.
+        let class = class.lookup(self)?;
+
+        let args: Vec<jvalue> = args.iter().map(|v| v.to_jni()).collect();
+        self.call_nonvirtual_method_unchecked(obj, class, (class, name, sig), parsed.ret, &args)
+    }
+
+    /// Calls an object method with a signature that was checked at compile
+    /// time, see `MethodSignature`.
+    ///
+    /// Unlike `call_method`, the signature doesn't need to be parsed, the
+    /// arguments are passed as a tuple of Rust values and the result is
+    /// returned with its Rust type, e.g. a `jint` or a `JString`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let index = env.call_method_typed(s, "indexOf", jni_sig!("(Ljava/lang/String;I)I"), (needle, 0))?;
+    /// ```
+    pub fn call_method_typed<O, S, A, Args, R>(
+        &self,
+        obj: O,
+        name: S,
+        sig: MethodSignature<Args, R>,
+        args: A,
+    ) -> Result<R::Output>
+    where
+        O: Into<JObject<'a>>,
+        S: Into<JNIString>,
+        A: JavaArgs<Args>,
+        R: JavaReturn<'a>,
+    {
+        let obj = obj.into();
+        non_null!(obj, "call_method_typed obj argument");
+
+        let class = self.auto_local(self.get_object_class(obj)?);
+
+        let args = args.into_jvalues();
+        let ret = self.call_method_unchecked(
+            obj,
+            (&class, name, sig.as_str()),
+            R::return_type(),
+            args.as_ref(),
+        )?;
+        R::from_jvalue(ret)
+    }
+
+    /// Calls a static method with a signature that was checked at compile
+    /// time, see `MethodSignature` and `call_method_typed`.
+    pub fn call_static_method_typed<'c, T, U, A, Args, R>(
+        &self,
+        class: T,
+        name: U,
+        sig: MethodSignature<Args, R>,
+        args: A,
+    ) -> Result<R::Output>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Into<JNIString>,
+        A: JavaArgs<Args>,
+        R: JavaReturn<'a>,
+    {
+        let class = class.lookup(self)?;
+
+        let args = args.into_jvalues();
+        let ret = self.call_static_method_unchecked(
+            class,
+            (class, name, sig.as_str()),
+            R::return_type(),
+            args.as_ref(),
+        )?;
+        R::from_jvalue(ret)
+    }
+
+    /// Calls a method like `call_method`, with arguments converted from Rust
+    /// values with `IntoJava`, given as a tuple. As the converted arguments
+    /// are objects, the signature must take objects, e.g. `java.lang.Integer`
+    /// for a boxed `jint`.
+    ///
+    /// The local references to the converted arguments are deleted after the
+    /// call.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// env.call_method_converted(map, "put", "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;", ("key", vec![1, 2]))?;
+    /// ```
+    pub fn call_method_converted<O, S, T, A>(
+        &self,
+        obj: O,
+        name: S,
+        sig: T,
+        args: A,
+    ) -> Result<JValue<'a>>
+    where
+        O: Into<JObject<'a>>,
+        S: Into<JNIString>,
+        T: Into<JNIString> + AsRef<str>,
+        A: IntoJavaArgs<'a>,
+    {
+        let args = args.into_java_args(self)?;
+        let args: Vec<JValue> = args.iter().map(|arg| arg.as_obj().into()).collect();
+        self.call_method(obj, name, sig, &args)
+    }
+
+    /// Calls a static method like `call_static_method`, with arguments
+    /// converted from Rust values with `IntoJava`, see
+    /// `call_method_converted`.
+    pub fn call_static_method_converted<'c, T, U, V, A>(
+        &self,
+        class: T,
+        name: U,
+        sig: V,
+        args: A,
+    ) -> Result<JValue<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Into<JNIString>,
+        V: Into<JNIString> + AsRef<str>,
+        A: IntoJavaArgs<'a>,
+    {
+        let args = args.into_java_args(self)?;
+        let args: Vec<JValue> = args.iter().map(|arg| arg.as_obj().into()).collect();
+        self.call_static_method(class, name, sig, &args)
+    }
+
+    /// Create a new object like `new_object`, with constructor arguments
+    /// converted from Rust values with `IntoJava`, see
+    /// `call_method_converted`.
+    pub fn new_object_converted<'c, T, U, A>(
+        &self,
+        class: T,
+        ctor_sig: U,
+        ctor_args: A,
+    ) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        U: Into<JNIString> + AsRef<str>,
+        A: IntoJavaArgs<'a>,
+    {
+        let args = ctor_args.into_java_args(self)?;
+        let args: Vec<JValue> = args.iter().map(|arg| arg.as_obj().into()).collect();
+        self.new_object(class, ctor_sig, &args)
+    }
+
     /// Create a new object using a constructor. This is done safely using
     /// checks similar to those in `call_static_method`.
     pub fn new_object<'c, T, U>(
@@ -959,6 +1926,33 @@ impl<'a> JNIEnv<'a> {
         ))
     }
 
+    /// Create a new object using a constructor with a signature that was
+    /// checked at compile time, see `MethodSignature` and `call_method_typed`.
+    pub fn new_object_typed<'c, T, A, Args>(
+        &self,
+        class: T,
+        ctor_sig: MethodSignature<Args, ()>,
+        ctor_args: A,
+    ) -> Result<JObject<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        A: JavaArgs<Args>,
+    {
+        let class = class.lookup(self)?;
+
+        let method_id: JMethodID = (class, ctor_sig.as_str()).lookup(self)?;
+
+        let jni_args = ctor_args.into_jvalues();
+
+        Ok(jni_non_null_call!(
+            self.internal,
+            NewObjectA,
+            class.into_inner(),
+            method_id.into_inner(),
+            jni_args.as_ref().as_ptr()
+        ))
+    }
+
     /// Cast a JObject to a `JList`. This won't throw exceptions or return errors
     /// in the event that the object isn't actually a list, but the methods on
     /// the resulting map object will.
@@ -967,6 +1961,73 @@ impl<'a> JNIEnv<'a> {
         JList::from_env(self, obj)
     }
 
+    /// Cast a JObject to a `JCollection`, checking that it is an instance of
+    /// `java.util.Collection`, and returning `Error::WrongObjectType` if not.
+    pub fn get_collection(&self, obj: JObject<'a>) -> Result<JCollection<'a, '_>> {
+        non_null!(obj, "get_collection obj argument");
+        if !self.is_instance_of(obj, "java/util/Collection")? {
+            return Err(Error::WrongObjectType("java.util.Collection"));
+        }
+        JCollection::from_env(self, obj)
+    }
+
+    /// Cast a JObject to a `JSet`, checking that it is an instance of
+    /// `java.util.Set`, and returning `Error::WrongObjectType` if not.
+    pub fn get_set(&self, obj: JObject<'a>) -> Result<JSet<'a, '_>> {
+        non_null!(obj, "get_set obj argument");
+        if !self.is_instance_of(obj, "java/util/Set")? {
+            return Err(Error::WrongObjectType("java.util.Set"));
+        }
+        JSet::from_env(self, obj)
+    }
+
+    /// Box a primitive value into an object of its `java.lang` wrapper class,
+    /// e.g. a `jint` into a `java.lang.Integer` with `Integer.valueOf`, to
+    /// pass it to a `JList` or a `JMap`.
+    ///
+    /// See `Boxable` for the supported types.
+    pub fn box_value<T: Boxable>(&self, value: T) -> Result<JObject<'a>> {
+        value.to_boxed(self)
+    }
+
+    /// Unbox the primitive value of an object of a `java.lang` wrapper class,
+    /// e.g. the `jint` value of a `java.lang.Integer`.
+    ///
+    /// If the object is `null`, an `Error::NullPtr` is returned, and if it
+    /// isn't an instance of the wrapper class of `T`, an
+    /// `Error::WrongObjectType` is returned.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let value: jint = env.unbox_value(list.get(0)?.unwrap())?;
+    /// ```
+    pub fn unbox_value<T: Boxable>(&self, obj: JObject<'a>) -> Result<T> {
+        T::from_boxed(self, obj)
+    }
+
+    /// Cast a JObject to a `JIterator`. This won't throw exceptions or return
+    /// errors in the event that the object isn't actually an iterator, but the
+    /// methods on the resulting iterator object will.
+    pub fn get_iterator(&self, obj: JObject<'a>) -> Result<JIterator<'a, '_>> {
+        non_null!(obj, "get_iterator obj argument");
+        JIterator::from_env(self, obj)
+    }
+
+    /// Get a `JIterable` to iterate over an `Iterable` (e.g. any collection),
+    /// a stream, or any other object with an `iterator()` method, or an
+    /// iterator itself.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// for elem in env.get_iterable(collection)?.iter()? {
+    ///     let s: String = env.get_string(elem.as_obj().into())?.into();
+    /// }
+    /// ```
+    pub fn get_iterable(&self, obj: JObject<'a>) -> Result<JIterable<'a, '_>> {
+        non_null!(obj, "get_iterable obj argument");
+        JIterable::from_env(self, obj)
+    }
+
     /// Cast a JObject to a JMap. This won't throw exceptions or return errors
     /// in the event that the object isn't actually a map, but the methods on
     /// the resulting map object will.
@@ -1029,22 +2090,168 @@ impl<'a> JNIEnv<'a> {
         Ok(())
     }
 
-    /// Create a new java string object from a rust string. This requires a
-    /// re-encoding of rusts *real* UTF-8 strings to java's modified UTF-8
-    /// format.
-    pub fn new_string<S: Into<JNIString>>(&self, from: S) -> Result<JString<'a>> {
-        let ffi_str = from.into();
-        Ok(jni_non_null_call!(
-            self.internal,
-            NewStringUTF,
-            ffi_str.as_ptr()
-        ))
+    /// Create a new java string object from a rust string. This requires a
+    /// re-encoding of rusts *real* UTF-8 strings to java's modified UTF-8
+    /// format.
+    pub fn new_string<S: Into<JNIString>>(&self, from: S) -> Result<JString<'a>> {
+        let ffi_str = from.into();
+        Ok(jni_non_null_call!(
+            self.internal,
+            NewStringUTF,
+            ffi_str.as_ptr()
+        ))
+    }
+
+    /// Create a new java string object from a slice of UTF-16 code units. No
+    /// re-encoding takes place.
+    pub fn new_string_utf16(&self, from: &[jchar]) -> Result<JString<'a>> {
+        Ok(jni_non_null_call!(
+            self.internal,
+            NewString,
+            from.as_ptr(),
+            from.len() as jsize
+        ))
+    }
+
+    /// Get the length of a java string in UTF-16 code units.
+    pub fn get_string_length(&self, obj: JString) -> Result<jsize> {
+        non_null!(obj, "get_string_length obj argument");
+        let len: jsize = jni_unchecked!(self.internal, GetStringLength, obj.into_inner());
+        Ok(len)
+    }
+
+    /// Get the length in bytes of the modified UTF-8 representation of a java
+    /// string, excluding the terminating nul byte.
+    pub fn get_string_utf_length(&self, obj: JString) -> Result<jsize> {
+        non_null!(obj, "get_string_utf_length obj argument");
+        let len: jsize = jni_unchecked!(self.internal, GetStringUTFLength, obj.into_inner());
+        Ok(len)
+    }
+
+    /// Copy UTF-16 code units of the java string from the `start` index to the
+    /// `buf` slice. The number of copied code units is equal to the `buf` length.
+    ///
+    /// # Errors
+    /// If `start` is negative _or_ `start + buf.len()` is greater than the
+    /// string length, a `StringIndexOutOfBoundsException` will be thrown and
+    /// `Error::JavaException` returned.
+    pub fn get_string_region(&self, obj: JString, start: jsize, buf: &mut [jchar]) -> Result<()> {
+        non_null!(obj, "get_string_region obj argument");
+        jni_void_call!(
+            self.internal,
+            GetStringRegion,
+            obj.into_inner(),
+            start,
+            buf.len() as jsize,
+            buf.as_mut_ptr()
+        );
+        Ok(())
+    }
+
+    /// Get a `JavaChars` from a JString. This gives access to the UTF-16
+    /// contents of the string without any transcoding.
+    ///
+    /// This entails a call to `GetStringChars`, which may or may not copy the
+    /// string contents.
+    pub fn get_string_utf16(&self, obj: JString<'a>) -> Result<JavaChars<'a, '_>> {
+        non_null!(obj, "get_string_utf16 obj argument");
+        JavaChars::from_env(self, obj)
+    }
+
+    /// Get a `JavaCharsCritical` from a JString. This gives access to the
+    /// UTF-16 contents of the string, most likely without copying them.
+    ///
+    /// This entails a call to `GetStringCritical`, see `JavaCharsCritical`
+    /// for details.
+    ///
+    /// # Safety
+    ///
+    /// No other JNI functions than the ones of the `CriticalEnv` handle (see
+    /// `JavaCharsCritical#split_env`) may be called on this thread until the
+    /// returned value is dropped, through this `JNIEnv` or any copy of it.
+    pub unsafe fn get_string_utf16_critical(
+        &self,
+        obj: JString<'a>,
+    ) -> Result<JavaCharsCritical<'a, '_>> {
+        non_null!(obj, "get_string_utf16_critical obj argument");
+        JavaCharsCritical::from_env(self, obj)
+    }
+
+    /// Get a pointer to the UTF-16 code units beneath a JString.
+    ///
+    /// # Attention
+    /// This will leak memory if `release_string_chars` is never called.
+    pub fn get_string_chars(&self, obj: JString) -> Result<*const jchar> {
+        non_null!(obj, "get_string_chars obj argument");
+        let ptr: *const jchar = jni_non_null_call!(
+            self.internal,
+            GetStringChars,
+            obj.into_inner(),
+            ::std::ptr::null::<jboolean>() as *mut jboolean
+        );
+        Ok(ptr)
+    }
+
+    /// Unpin the array returned by `get_string_chars`.
+    ///
+    /// # Safety
+    ///
+    /// The behaviour is undefined if the array isn't returned by the `get_string_chars`
+    /// function.
+    #[allow(unused_unsafe)]
+    pub unsafe fn release_string_chars(&self, obj: JString, arr: *const jchar) -> Result<()> {
+        non_null!(obj, "release_string_chars obj argument");
+        // This method is safe to call in case of pending exceptions (see the chapter 2 of the spec)
+        jni_unchecked!(self.internal, ReleaseStringChars, obj.into_inner(), arr);
+        Ok(())
+    }
+
+    /// Get a pointer to the UTF-16 code units beneath a JString, entering a
+    /// critical region.
+    ///
+    /// # Safety
+    ///
+    /// No other JNI functions may be called, and the thread must not block,
+    /// until `release_string_critical` is called.
+    ///
+    /// # Attention
+    /// This will leak memory if `release_string_critical` is never called.
+    pub unsafe fn get_string_critical(&self, obj: JString) -> Result<*const jchar> {
+        non_null!(obj, "get_string_critical obj argument");
+        let ptr: *const jchar = jni_unchecked!(
+            self.internal,
+            GetStringCritical,
+            obj.into_inner(),
+            ::std::ptr::null::<jboolean>() as *mut jboolean
+        );
+        // Exceptions can't be checked from within the critical region, a
+        // null pointer is the only sign of failure.
+        non_null!(ptr, "GetStringCritical result");
+        Ok(ptr)
+    }
+
+    /// Unpin the array returned by `get_string_critical`, leaving the critical
+    /// region.
+    ///
+    /// # Safety
+    ///
+    /// The behaviour is undefined if the array isn't returned by the `get_string_critical`
+    /// function.
+    #[allow(unused_unsafe)]
+    pub unsafe fn release_string_critical(&self, obj: JString, arr: *const jchar) -> Result<()> {
+        non_null!(obj, "release_string_critical obj argument");
+        jni_unchecked!(self.internal, ReleaseStringCritical, obj.into_inner(), arr);
+        Ok(())
     }
 
     /// Get the length of a java array
-    pub fn get_array_length(&self, array: jarray) -> Result<jsize> {
+    pub fn get_array_length<O>(&self, array: O) -> Result<jsize>
+    where
+        O: Into<JObject<'a>>,
+    {
+        let array = array.into();
         non_null!(array, "get_array_length array argument");
-        let len: jsize = jni_unchecked!(self.internal, GetArrayLength, array);
+        let len: jsize = jni_unchecked!(self.internal, GetArrayLength, array.into_inner());
         Ok(len)
     }
 
@@ -1061,7 +2268,7 @@ impl<'a> JNIEnv<'a> {
         length: jsize,
         element_class: T,
         initial_element: U,
-    ) -> Result<jobjectArray>
+    ) -> Result<JObjectArray<'a>>
     where
         T: Desc<'a, JClass<'c>>,
         U: Into<JObject<'a>>,
@@ -1076,20 +2283,92 @@ impl<'a> JNIEnv<'a> {
         ))
     }
 
+    /// Construct a new array holding objects in class `element_class`, with
+    /// the given objects as elements, e.g. the elements of a `Vec<JObject>` or
+    /// a `&[GlobalRef]`.
+    pub fn object_array_from_iter<'c, 'o, T, I>(
+        &self,
+        element_class: T,
+        objects: I,
+    ) -> Result<JObjectArray<'a>>
+    where
+        T: Desc<'a, JClass<'c>>,
+        I: IntoIterator,
+        I::Item: Into<JObject<'o>>,
+    {
+        let class = element_class.lookup(self)?;
+        let objects: Vec<JObject<'o>> = objects.into_iter().map(Into::into).collect();
+        let array = self.new_object_array(objects.len() as jsize, class, JObject::null())?;
+        for (index, obj) in objects.into_iter().enumerate() {
+            // Decouple the lifetime of the element from the one of the array.
+            let obj = JObject::from(obj.into_inner());
+            self.set_object_array_element(array, index as jsize, obj)?;
+        }
+        Ok(array)
+    }
+
+    /// Create a new `String[]` array from a slice of Rust strings.
+    pub fn new_string_array<S: AsRef<str>>(&self, strings: &[S]) -> Result<JObjectArray<'a>> {
+        let array =
+            self.new_object_array(strings.len() as jsize, "java/lang/String", JObject::null())?;
+        for (index, s) in strings.iter().enumerate() {
+            let s = self.auto_local(self.new_string(s.as_ref())?);
+            self.set_object_array_element(array, index as jsize, s.as_obj())?;
+        }
+        Ok(array)
+    }
+
+    /// Read a `String[]` array into a `Vec<String>`.
+    ///
+    /// If an element is `null`, an `Error::NullPtr` is returned.
+    pub fn get_string_array(&self, array: JObjectArray<'a>) -> Result<Vec<String>> {
+        non_null!(array, "get_string_array array argument");
+        let len = self.get_array_length(array)?;
+        let mut strings = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            let s = self.auto_local(self.get_object_array_element(array, index)?);
+            strings.push(self.get_string(s.as_obj().into())?.into());
+        }
+        Ok(strings)
+    }
+
+    /// Get an iterator over the elements of an object array, see
+    /// `JObjectArrayIter`.
+    pub fn get_object_array_iter(
+        &self,
+        array: JObjectArray<'a>,
+    ) -> Result<JObjectArrayIter<'a, '_>> {
+        non_null!(array, "get_object_array_iter array argument");
+        JObjectArrayIter::from_env(self, array)
+    }
+
     /// Returns an element of the `jobjectArray` array.
+    ///
+    /// If the index is out of bounds, an `Error::IndexOutOfBounds` is
+    /// returned, and no exception is left pending.
     pub fn get_object_array_element(
         &self,
-        array: jobjectArray,
+        array: JObjectArray<'a>,
         index: jsize,
     ) -> Result<JObject<'a>> {
         non_null!(array, "get_object_array_element array argument");
-        Ok(jni_non_void_call!(self.internal, GetObjectArrayElement, array, index).into())
+        let elem = jni_unchecked!(
+            self.internal,
+            GetObjectArrayElement,
+            array.into_inner(),
+            index
+        );
+        self.check_array_index_exception(index)?;
+        Ok(elem.into())
     }
 
     /// Sets an element of the `jobjectArray` array.
+    ///
+    /// If the index is out of bounds, an `Error::IndexOutOfBounds` is
+    /// returned, and no exception is left pending.
     pub fn set_object_array_element<O>(
         &self,
-        array: jobjectArray,
+        array: JObjectArray<'a>,
         index: jsize,
         value: O,
     ) -> Result<()>
@@ -1097,97 +2376,132 @@ impl<'a> JNIEnv<'a> {
         O: Into<JObject<'a>>,
     {
         non_null!(array, "set_object_array_element array argument");
-        jni_void_call!(
+        jni_unchecked!(
             self.internal,
             SetObjectArrayElement,
-            array,
+            array.into_inner(),
             index,
             value.into().into_inner()
         );
-        Ok(())
+        self.check_array_index_exception(index)
+    }
+
+    // Maps a pending `ArrayIndexOutOfBoundsException` to
+    // `Error::IndexOutOfBounds`, clearing it, and any other pending exception
+    // to the usual error.
+    fn check_array_index_exception(&self, index: jsize) -> Result<()> {
+        if !self.exception_check()? {
+            return Ok(());
+        }
+        let throwable = self.auto_local(self.exception_occurred()?);
+        self.exception_clear()?;
+        if self.is_instance_of(&throwable, "java/lang/ArrayIndexOutOfBoundsException")? {
+            return Err(Error::IndexOutOfBounds(index));
+        }
+        self.throw(JThrowable::from(throwable.as_obj()))?;
+        Err(self.pending_exception_error())
     }
 
     /// Create a new java byte array from a rust byte slice.
-    pub fn byte_array_from_slice(&self, buf: &[u8]) -> Result<jbyteArray> {
-        let length = buf.len() as i32;
-        let bytes: jbyteArray = self.new_byte_array(length)?;
-        jni_unchecked!(
-            self.internal,
-            SetByteArrayRegion,
-            bytes,
-            0,
-            length,
-            buf.as_ptr() as *const i8
-        );
-        Ok(bytes)
+    ///
+    /// See also [`array_from_slice`](struct.JNIEnv.html#method.array_from_slice)
+    pub fn byte_array_from_slice(&self, buf: &[u8]) -> Result<JByteArray<'a>> {
+        self.array_from_slice(buf)
     }
 
     /// Converts a java byte array to a rust vector of bytes.
-    pub fn convert_byte_array(&self, array: jbyteArray) -> Result<Vec<u8>> {
+    ///
+    /// See also [`convert_array`](struct.JNIEnv.html#method.convert_array)
+    pub fn convert_byte_array(&self, array: JByteArray<'a>) -> Result<Vec<u8>> {
         non_null!(array, "convert_byte_array array argument");
-        let length = jni_non_void_call!(self.internal, GetArrayLength, array);
-        let mut vec = vec![0u8; length as usize];
-        jni_unchecked!(
-            self.internal,
-            GetByteArrayRegion,
-            array,
-            0,
-            length,
-            vec.as_mut_ptr() as *mut i8
-        );
-        Ok(vec)
+        self.convert_array(array)
     }
 
-    /// Create a new java boolean array of supplied length.
-    pub fn new_boolean_array(&self, length: jsize) -> Result<jbooleanArray> {
-        let array: jbooleanArray = jni_non_null_call!(self.internal, NewBooleanArray, length);
+    /// Create a new java primitive array from a rust slice, e.g. an `int[]`
+    /// from a `&[jint]`, or a `boolean[]` from a `&[bool]`.
+    ///
+    /// See `ArrayElement` for the supported element types.
+    pub fn array_from_slice<T: ArrayElement>(
+        &self,
+        buf: &[T],
+    ) -> Result<JPrimitiveArray<'a, T::Elem>> {
+        let elems = T::to_elems(buf);
+        let array = self.new_primitive_array(elems.len() as jsize)?;
+        self.set_array_region(array, 0, &elems)?;
         Ok(array)
     }
 
+    /// Converts a java primitive array to a rust vector, e.g. an `int[]` to a
+    /// `Vec<jint>`, or a `boolean[]` to a `Vec<bool>`.
+    ///
+    /// See `ArrayElement` for the supported element types.
+    pub fn convert_array<T: ArrayElement>(
+        &self,
+        array: JPrimitiveArray<'a, T::Elem>,
+    ) -> Result<Vec<T>> {
+        non_null!(array, "convert_array array argument");
+        let length = self.get_array_length(array)?;
+        let mut elems = vec![T::Elem::default(); length as usize];
+        self.get_array_region(array, 0, &mut elems)?;
+        Ok(T::from_elems(elems))
+    }
+
+    /// Create a new java array of supplied length, with elements of the given
+    /// primitive type, e.g. `env.new_primitive_array::<jint>(length)` for an
+    /// `int[]`.
+    ///
+    /// Prefer to use the convenience wrappers, e.g.
+    /// [`new_int_array`](struct.JNIEnv.html#method.new_int_array).
+    pub fn new_primitive_array<T: TypeArray>(
+        &self,
+        length: jsize,
+    ) -> Result<JPrimitiveArray<'a, T>> {
+        Ok(T::new_array(self, length)?.into())
+    }
+
+    /// Create a new java boolean array of supplied length.
+    pub fn new_boolean_array(&self, length: jsize) -> Result<JBooleanArray<'a>> {
+        self.new_primitive_array(length)
+    }
+
     /// Create a new java byte array of supplied length.
-    pub fn new_byte_array(&self, length: jsize) -> Result<jbyteArray> {
-        let array: jbyteArray = jni_non_null_call!(self.internal, NewByteArray, length);
-        Ok(array)
+    pub fn new_byte_array(&self, length: jsize) -> Result<JByteArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java char array of supplied length.
-    pub fn new_char_array(&self, length: jsize) -> Result<jcharArray> {
-        let array: jcharArray = jni_non_null_call!(self.internal, NewCharArray, length);
-        Ok(array)
+    pub fn new_char_array(&self, length: jsize) -> Result<JCharArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java short array of supplied length.
-    pub fn new_short_array(&self, length: jsize) -> Result<jshortArray> {
-        let array: jshortArray = jni_non_null_call!(self.internal, NewShortArray, length);
-        Ok(array)
+    pub fn new_short_array(&self, length: jsize) -> Result<JShortArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java int array of supplied length.
-    pub fn new_int_array(&self, length: jsize) -> Result<jintArray> {
-        let array: jintArray = jni_non_null_call!(self.internal, NewIntArray, length);
-        Ok(array)
+    pub fn new_int_array(&self, length: jsize) -> Result<JIntArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java long array of supplied length.
-    pub fn new_long_array(&self, length: jsize) -> Result<jlongArray> {
-        let array: jlongArray = jni_non_null_call!(self.internal, NewLongArray, length);
-        Ok(array)
+    pub fn new_long_array(&self, length: jsize) -> Result<JLongArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java float array of supplied length.
-    pub fn new_float_array(&self, length: jsize) -> Result<jfloatArray> {
-        let array: jfloatArray = jni_non_null_call!(self.internal, NewFloatArray, length);
-        Ok(array)
+    pub fn new_float_array(&self, length: jsize) -> Result<JFloatArray<'a>> {
+        self.new_primitive_array(length)
     }
 
     /// Create a new java double array of supplied length.
-    pub fn new_double_array(&self, length: jsize) -> Result<jdoubleArray> {
-        let array: jdoubleArray = jni_non_null_call!(self.internal, NewDoubleArray, length);
-        Ok(array)
+    pub fn new_double_array(&self, length: jsize) -> Result<JDoubleArray<'a>> {
+        self.new_primitive_array(length)
     }
 
-    /// Copy elements of the java boolean array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
+    /// Copy elements of the java primitive array from the `start` index to
+    /// the `buf` slice. The number of copied elements is equal to the `buf`
+    /// length.
     ///
     /// # Errors
     /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
@@ -1195,26 +2509,18 @@ impl<'a> JNIEnv<'a> {
     /// and `Err` is returned.
     ///
     /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
-    pub fn get_boolean_array_region(
+    pub fn get_array_region<T: TypeArray>(
         &self,
-        array: jbooleanArray,
+        array: JPrimitiveArray<'a, T>,
         start: jsize,
-        buf: &mut [jboolean],
+        buf: &mut [T],
     ) -> Result<()> {
-        non_null!(array, "get_boolean_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetBooleanArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        non_null!(array, "get_array_region array argument");
+        T::get_region(self, *array, start, buf)
     }
 
-    /// Copy elements of the java byte array from the `start` index to the `buf`
-    /// slice. The number of copied elements is equal to the `buf` length.
+    /// Copy the contents of the `buf` slice to the java primitive array at
+    /// the `start` index.
     ///
     /// # Errors
     /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
@@ -1222,339 +2528,222 @@ impl<'a> JNIEnv<'a> {
     /// and `Err` is returned.
     ///
     /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    pub fn set_array_region<T: TypeArray>(
+        &self,
+        array: JPrimitiveArray<'a, T>,
+        start: jsize,
+        buf: &[T],
+    ) -> Result<()> {
+        non_null!(array, "set_array_region array argument");
+        T::set_region(self, *array, start, buf)
+    }
+
+    /// Copy elements of the java boolean array from the `start` index to the
+    /// `buf` slice.
+    ///
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
+    pub fn get_boolean_array_region(
+        &self,
+        array: JBooleanArray<'a>,
+        start: jsize,
+        buf: &mut [jboolean],
+    ) -> Result<()> {
+        self.get_array_region(array, start, buf)
+    }
+
+    /// Copy elements of the java byte array from the `start` index to the
+    /// `buf` slice.
+    ///
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_byte_array_region(
         &self,
-        array: jbyteArray,
+        array: JByteArray<'a>,
         start: jsize,
         buf: &mut [jbyte],
     ) -> Result<()> {
-        non_null!(array, "get_byte_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetByteArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java char array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
-    ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
+    /// `buf` slice.
     ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_char_array_region(
         &self,
-        array: jcharArray,
+        array: JCharArray<'a>,
         start: jsize,
         buf: &mut [jchar],
     ) -> Result<()> {
-        non_null!(array, "get_char_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetCharArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java short array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
-    ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
+    /// `buf` slice.
     ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_short_array_region(
         &self,
-        array: jshortArray,
+        array: JShortArray<'a>,
         start: jsize,
         buf: &mut [jshort],
     ) -> Result<()> {
-        non_null!(array, "get_short_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetShortArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java int array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
-    ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
+    /// `buf` slice.
     ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_int_array_region(
         &self,
-        array: jintArray,
+        array: JIntArray<'a>,
         start: jsize,
         buf: &mut [jint],
     ) -> Result<()> {
-        non_null!(array, "get_int_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetIntArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java long array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
+    /// `buf` slice.
     ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
-    ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_long_array_region(
         &self,
-        array: jlongArray,
+        array: JLongArray<'a>,
         start: jsize,
         buf: &mut [jlong],
     ) -> Result<()> {
-        non_null!(array, "get_long_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetLongArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java float array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
-    ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
+    /// `buf` slice.
     ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_float_array_region(
         &self,
-        array: jfloatArray,
+        array: JFloatArray<'a>,
         start: jsize,
         buf: &mut [jfloat],
     ) -> Result<()> {
-        non_null!(array, "get_float_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetFloatArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy elements of the java double array from the `start` index to the
-    /// `buf` slice. The number of copied elements is equal to the `buf` length.
-    ///
-    /// # Errors
-    /// If `start` is negative _or_ `start + buf.len()` is greater than [`array.length`]
-    /// then no elements are copied, an `ArrayIndexOutOfBoundsException` is thrown,
-    /// and `Err` is returned.
+    /// `buf` slice.
     ///
-    /// [`array.length`]: struct.JNIEnv.html#method.get_array_length
+    /// See also [`get_array_region`](struct.JNIEnv.html#method.get_array_region)
     pub fn get_double_array_region(
         &self,
-        array: jdoubleArray,
+        array: JDoubleArray<'a>,
         start: jsize,
         buf: &mut [jdouble],
     ) -> Result<()> {
-        non_null!(array, "get_double_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            GetDoubleArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_mut_ptr()
-        );
-        Ok(())
+        self.get_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java boolean array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_boolean_array_region(
         &self,
-        array: jbooleanArray,
+        array: JBooleanArray<'a>,
         start: jsize,
         buf: &[jboolean],
     ) -> Result<()> {
-        non_null!(array, "set_boolean_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetBooleanArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java byte array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_byte_array_region(
         &self,
-        array: jbyteArray,
+        array: JByteArray<'a>,
         start: jsize,
         buf: &[jbyte],
     ) -> Result<()> {
-        non_null!(array, "set_byte_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetByteArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java char array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_char_array_region(
         &self,
-        array: jcharArray,
+        array: JCharArray<'a>,
         start: jsize,
         buf: &[jchar],
     ) -> Result<()> {
-        non_null!(array, "set_char_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetCharArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java short array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_short_array_region(
         &self,
-        array: jshortArray,
+        array: JShortArray<'a>,
         start: jsize,
         buf: &[jshort],
     ) -> Result<()> {
-        non_null!(array, "set_short_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetShortArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java int array at the
     /// `start` index.
-    pub fn set_int_array_region(&self, array: jintArray, start: jsize, buf: &[jint]) -> Result<()> {
-        non_null!(array, "set_int_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetIntArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
+    pub fn set_int_array_region(
+        &self,
+        array: JIntArray<'a>,
+        start: jsize,
+        buf: &[jint],
+    ) -> Result<()> {
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java long array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_long_array_region(
         &self,
-        array: jlongArray,
+        array: JLongArray<'a>,
         start: jsize,
         buf: &[jlong],
     ) -> Result<()> {
-        non_null!(array, "set_long_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetLongArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java float array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_float_array_region(
         &self,
-        array: jfloatArray,
+        array: JFloatArray<'a>,
         start: jsize,
         buf: &[jfloat],
     ) -> Result<()> {
-        non_null!(array, "set_float_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetFloatArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Copy the contents of the `buf` slice to the java double array at the
     /// `start` index.
+    ///
+    /// See also [`set_array_region`](struct.JNIEnv.html#method.set_array_region)
     pub fn set_double_array_region(
         &self,
-        array: jdoubleArray,
+        array: JDoubleArray<'a>,
         start: jsize,
         buf: &[jdouble],
     ) -> Result<()> {
-        non_null!(array, "set_double_array_region array argument");
-        jni_void_call!(
-            self.internal,
-            SetDoubleArrayRegion,
-            array,
-            start,
-            buf.len() as jsize,
-            buf.as_ptr()
-        );
-        Ok(())
+        self.set_array_region(array, start, buf)
     }
 
     /// Get a field without checking the provided type against the actual field.
@@ -2009,7 +3198,7 @@ impl<'a> JNIEnv<'a> {
     /// And the associated [`AutoArray`](struct.objects.AutoArray) struct.
     pub fn get_array_elements<T: TypeArray>(
         &self,
-        array: jarray,
+        array: JPrimitiveArray<'a, T>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<T>> {
         non_null!(array, "get_array_elements array argument");
@@ -2019,7 +3208,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_int_array_elements(
         &self,
-        array: jintArray,
+        array: JIntArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jint>> {
         self.get_array_elements(array, mode)
@@ -2028,7 +3217,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_long_array_elements(
         &self,
-        array: jlongArray,
+        array: JLongArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jlong>> {
         self.get_array_elements(array, mode)
@@ -2037,7 +3226,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_byte_array_elements(
         &self,
-        array: jbyteArray,
+        array: JByteArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jbyte>> {
         self.get_array_elements(array, mode)
@@ -2046,7 +3235,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_boolean_array_elements(
         &self,
-        array: jbooleanArray,
+        array: JBooleanArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jboolean>> {
         self.get_array_elements(array, mode)
@@ -2055,7 +3244,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_char_array_elements(
         &self,
-        array: jcharArray,
+        array: JCharArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jchar>> {
         self.get_array_elements(array, mode)
@@ -2064,7 +3253,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_short_array_elements(
         &self,
-        array: jshortArray,
+        array: JShortArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jshort>> {
         self.get_array_elements(array, mode)
@@ -2073,7 +3262,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_float_array_elements(
         &self,
-        array: jfloatArray,
+        array: JFloatArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jfloat>> {
         self.get_array_elements(array, mode)
@@ -2082,7 +3271,7 @@ impl<'a> JNIEnv<'a> {
     /// See also [`get_array_elements`](struct.JNIEnv.html#method.get_array_elements)
     pub fn get_double_array_elements(
         &self,
-        array: jdoubleArray,
+        array: JDoubleArray<'a>,
         mode: ReleaseMode,
     ) -> Result<AutoArray<jdouble>> {
         self.get_array_elements(array, mode)
@@ -2105,28 +3294,29 @@ impl<'a> JNIEnv<'a> {
     /// ReleasePrimitiveArrayCritical is called; which happens at AutoPrimitiveArray
     /// destruction.
     ///
+    /// Use `AutoPrimitiveArray#split_env` to get other critical arrays or
+    /// strings while the array is held, which are given as `CriticalArray`s
+    /// and `CriticalString`s so that their lengths are queried beforehand.
+    ///
     /// If the given array is `null`, an `Error::NullPtr` is returned.
     ///
     /// See also [`get_byte_array_elements`](struct.JNIEnv.html#method.get_array_elements)
-    pub fn get_primitive_array_critical(
+    ///
+    /// # Safety
+    ///
+    /// No other JNI functions than the ones of the `CriticalEnv` handle may be
+    /// called on this thread until the array is released, through this
+    /// `JNIEnv` or any copy of it. The array must not be held already, e.g.
+    /// by an `AutoArray`, as the JVM may hand out the same memory, which can
+    /// be accessed mutably through the `AutoPrimitiveArray`.
+    pub unsafe fn get_primitive_array_critical<T: TypeArray>(
         &self,
-        array: jarray,
+        array: JPrimitiveArray<'a, T>,
         mode: ReleaseMode,
-    ) -> Result<AutoPrimitiveArray> {
+    ) -> Result<AutoPrimitiveArray<'a, '_, T>> {
         non_null!(array, "get_primitive_array_critical array argument");
-        let mut is_copy: jboolean = 0xff;
-        // Even though this method may throw OoME, use `jni_unchecked`
-        // instead of `jni_non_null_call` to remove (a slight) overhead
-        // of exception checking. An error will still be detected as a `null`
-        // result inside AutoPrimitiveArray ctor; and, as this method is unlikely
-        // to create a copy, an OoME is highly unlikely.
-        let ptr = jni_unchecked!(
-            self.internal,
-            GetPrimitiveArrayCritical,
-            array,
-            &mut is_copy
-        );
-        AutoPrimitiveArray::new(self, array.into(), ptr, mode, is_copy == sys::JNI_TRUE)
+        let len = self.get_array_length(array)?;
+        AutoPrimitiveArray::new(self, array, len, mode)
     }
 }
 
@@ -84,7 +84,9 @@ macro_rules! check_exception {
         let check = { jni_unchecked!($jnienv, ExceptionCheck) } == $crate::sys::JNI_TRUE;
         if check {
             log::trace!("exception found, returning error");
-            return Err($crate::errors::Error::JavaException);
+            return Err(unsafe { $crate::JNIEnv::from_raw($jnienv) }
+                .map(|env| env.pending_exception_error())
+                .unwrap_or($crate::errors::Error::JavaException));
         }
         log::trace!("no exception found");
     };
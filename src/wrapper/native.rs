@@ -0,0 +1,207 @@
+use std::{any::Any, ptr};
+
+use crate::{
+    errors::ToException,
+    objects::{
+        JByteBuffer, JClass, JObject, JObjectArray, JPrimitiveArray, JString, JThrowable, TypeArray,
+    },
+    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort},
+    JNIEnv,
+};
+
+/// A type that can be used as an argument of a native method implemented in
+/// Rust.
+///
+/// This converts from the raw value the JVM passes through the native ABI
+/// (e.g. a `jobject`) to a Rust type (e.g. a `JString`). Implemented for the
+/// primitive `sys` types, `bool` (passed as a `jboolean`), raw `jobject`s and
+/// the object wrappers in `jni::objects`.
+pub trait NativeArg: Sized {
+    /// The raw type the JVM passes for this argument.
+    type Abi;
+
+    /// Converts the raw argument to this type.
+    ///
+    /// # Safety
+    ///
+    /// `abi` must be an argument the JVM passed to a native method, and the
+    /// result must not outlive that native method call.
+    unsafe fn from_abi(abi: Self::Abi) -> Self;
+}
+
+/// A type that can be returned from a native method implemented in Rust.
+///
+/// This converts a Rust type (e.g. a `JString`) to the raw value the JVM
+/// expects through the native ABI (e.g. a `jobject`).
+///
+/// `Result<R, E>` can be returned when the error implements `ToException`:
+/// an `Err` is thrown as a Java exception, see `JNIEnv::catch_and_throw`.
+pub trait NativeReturn: Sized {
+    /// The raw type returned to the JVM.
+    type Abi;
+
+    /// Converts this value to the raw return value.
+    fn into_abi(self, env: &JNIEnv) -> Self::Abi;
+
+    /// The raw value to return when the native method fails with a pending
+    /// Java exception. The JVM ignores it, so this is `0`, `false`, `null` or
+    /// `()`.
+    fn abi_default() -> Self::Abi;
+}
+
+macro_rules! impl_native_identity {
+    ( $( $type:ty => $default:expr ),* ) => {
+        $(
+            impl NativeArg for $type {
+                type Abi = $type;
+
+                unsafe fn from_abi(abi: Self::Abi) -> Self {
+                    abi
+                }
+            }
+
+            impl NativeReturn for $type {
+                type Abi = $type;
+
+                fn into_abi(self, _env: &JNIEnv) -> Self::Abi {
+                    self
+                }
+
+                fn abi_default() -> Self::Abi {
+                    $default
+                }
+            }
+        )*
+    };
+}
+
+impl_native_identity!(
+    jboolean => 0,
+    jbyte => 0,
+    jchar => 0,
+    jshort => 0,
+    jint => 0,
+    jlong => 0,
+    jfloat => 0.0,
+    jdouble => 0.0,
+    jobject => ptr::null_mut()
+);
+
+// The JVM passes a `jboolean`, which may in principle be any byte, so it
+// isn't read as a `bool` directly.
+impl NativeArg for bool {
+    type Abi = jboolean;
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi != 0
+    }
+}
+
+impl NativeReturn for bool {
+    type Abi = jboolean;
+
+    fn into_abi(self, _env: &JNIEnv) -> Self::Abi {
+        jboolean::from(self)
+    }
+
+    fn abi_default() -> Self::Abi {
+        0
+    }
+}
+
+macro_rules! impl_native_object {
+    ( $( $type:ident ),* ) => {
+        $(
+            impl<'a> NativeArg for $type<'a> {
+                type Abi = jobject;
+
+                unsafe fn from_abi(abi: Self::Abi) -> Self {
+                    abi.into()
+                }
+            }
+
+            impl<'a> NativeReturn for $type<'a> {
+                type Abi = jobject;
+
+                fn into_abi(self, _env: &JNIEnv) -> Self::Abi {
+                    self.into_inner()
+                }
+
+                fn abi_default() -> Self::Abi {
+                    ptr::null_mut()
+                }
+            }
+        )*
+    };
+}
+
+impl_native_object!(
+    JObject,
+    JClass,
+    JString,
+    JThrowable,
+    JByteBuffer,
+    JObjectArray
+);
+
+impl<'a, T: TypeArray> NativeArg for JPrimitiveArray<'a, T> {
+    type Abi = jobject;
+
+    unsafe fn from_abi(abi: Self::Abi) -> Self {
+        abi.into()
+    }
+}
+
+impl<'a, T: TypeArray> NativeReturn for JPrimitiveArray<'a, T> {
+    type Abi = jobject;
+
+    fn into_abi(self, _env: &JNIEnv) -> Self::Abi {
+        self.into_inner()
+    }
+
+    fn abi_default() -> Self::Abi {
+        ptr::null_mut()
+    }
+}
+
+impl NativeReturn for () {
+    type Abi = ();
+
+    fn into_abi(self, _env: &JNIEnv) -> Self::Abi {}
+
+    fn abi_default() -> Self::Abi {}
+}
+
+impl<R, E> NativeReturn for Result<R, E>
+where
+    R: NativeReturn,
+    E: ToException,
+{
+    type Abi = R::Abi;
+
+    fn into_abi(self, env: &JNIEnv) -> Self::Abi {
+        match self {
+            Ok(ret) => ret.into_abi(env),
+            Err(e) => {
+                env.throw_unless_pending(&e);
+                R::abi_default()
+            }
+        }
+    }
+
+    fn abi_default() -> Self::Abi {
+        R::abi_default()
+    }
+}
+
+// Extracts the message from a panic payload, which is a `&str` or a `String`
+// for panics raised with `panic!`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "Rust panic"
+    }
+}
@@ -1,14 +1,24 @@
-use crate::sys::jsize;
+use crate::sys::{jarray, jsize};
 use log::error;
 
-use std::ptr::NonNull;
+use std::{ptr::NonNull, slice};
 
 use crate::objects::release_mode::ReleaseMode;
 use crate::sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort};
 use crate::{errors::*, objects::JObject, sys, JNIEnv};
 
-/// Trait to define type array access/release
-pub trait TypeArray {
+/// Trait to define type array creation/access/release, implemented for the
+/// eight Java primitive types.
+pub trait TypeArray: Sized {
+    /// creator
+    fn new_array(env: &JNIEnv, length: jsize) -> Result<jarray>;
+
+    /// region getter
+    fn get_region(env: &JNIEnv, obj: JObject, start: jsize, buf: &mut [Self]) -> Result<()>;
+
+    /// region setter
+    fn set_region(env: &JNIEnv, obj: JObject, start: jsize, buf: &[Self]) -> Result<()>;
+
     /// getter
     fn get(env: &JNIEnv, obj: JObject, is_copy: &mut jboolean) -> Result<*mut Self>;
 
@@ -18,9 +28,56 @@ pub trait TypeArray {
 
 // TypeArray builder
 macro_rules! type_array {
-    ( $jni_type:ty, $jni_get:tt, $jni_release:tt ) => {
+    (
+        $jni_type:ty,
+        $jni_new:tt,
+        $jni_get_region:tt,
+        $jni_set_region:tt,
+        $jni_get:tt,
+        $jni_release:tt
+    ) => {
         /// $jni_type array access/release impl
         impl TypeArray for $jni_type {
+            /// Create Java $jni_type array
+            fn new_array(env: &JNIEnv, length: jsize) -> Result<jarray> {
+                let internal = env.get_native_interface();
+                let array = jni_non_null_call!(internal, $jni_new, length);
+                Ok(array)
+            }
+
+            /// Copy a region of Java $jni_type array
+            fn get_region(
+                env: &JNIEnv,
+                obj: JObject,
+                start: jsize,
+                buf: &mut [Self],
+            ) -> Result<()> {
+                let internal = env.get_native_interface();
+                jni_void_call!(
+                    internal,
+                    $jni_get_region,
+                    *obj,
+                    start,
+                    buf.len() as jsize,
+                    buf.as_mut_ptr()
+                );
+                Ok(())
+            }
+
+            /// Set a region of Java $jni_type array
+            fn set_region(env: &JNIEnv, obj: JObject, start: jsize, buf: &[Self]) -> Result<()> {
+                let internal = env.get_native_interface();
+                jni_void_call!(
+                    internal,
+                    $jni_set_region,
+                    *obj,
+                    start,
+                    buf.len() as jsize,
+                    buf.as_ptr()
+                );
+                Ok(())
+            }
+
             /// Get Java $jni_type array
             fn get(env: &JNIEnv, obj: JObject, is_copy: &mut jboolean) -> Result<*mut Self> {
                 let internal = env.get_native_interface();
@@ -44,29 +101,83 @@ macro_rules! type_array {
     };
 }
 
-type_array!(jint, GetIntArrayElements, ReleaseIntArrayElements);
-type_array!(jlong, GetLongArrayElements, ReleaseLongArrayElements);
-type_array!(jbyte, GetByteArrayElements, ReleaseByteArrayElements);
+type_array!(
+    jint,
+    NewIntArray,
+    GetIntArrayRegion,
+    SetIntArrayRegion,
+    GetIntArrayElements,
+    ReleaseIntArrayElements
+);
+type_array!(
+    jlong,
+    NewLongArray,
+    GetLongArrayRegion,
+    SetLongArrayRegion,
+    GetLongArrayElements,
+    ReleaseLongArrayElements
+);
+type_array!(
+    jbyte,
+    NewByteArray,
+    GetByteArrayRegion,
+    SetByteArrayRegion,
+    GetByteArrayElements,
+    ReleaseByteArrayElements
+);
 type_array!(
     jboolean,
+    NewBooleanArray,
+    GetBooleanArrayRegion,
+    SetBooleanArrayRegion,
     GetBooleanArrayElements,
     ReleaseBooleanArrayElements
 );
-type_array!(jchar, GetCharArrayElements, ReleaseCharArrayElements);
-type_array!(jshort, GetShortArrayElements, ReleaseShortArrayElements);
-type_array!(jfloat, GetFloatArrayElements, ReleaseFloatArrayElements);
-type_array!(jdouble, GetDoubleArrayElements, ReleaseDoubleArrayElements);
+type_array!(
+    jchar,
+    NewCharArray,
+    GetCharArrayRegion,
+    SetCharArrayRegion,
+    GetCharArrayElements,
+    ReleaseCharArrayElements
+);
+type_array!(
+    jshort,
+    NewShortArray,
+    GetShortArrayRegion,
+    SetShortArrayRegion,
+    GetShortArrayElements,
+    ReleaseShortArrayElements
+);
+type_array!(
+    jfloat,
+    NewFloatArray,
+    GetFloatArrayRegion,
+    SetFloatArrayRegion,
+    GetFloatArrayElements,
+    ReleaseFloatArrayElements
+);
+type_array!(
+    jdouble,
+    NewDoubleArray,
+    GetDoubleArrayRegion,
+    SetDoubleArrayRegion,
+    GetDoubleArrayElements,
+    ReleaseDoubleArrayElements
+);
 
 /// Auto-release wrapper for pointer-based generic arrays.
 ///
 /// This wrapper is used to wrap pointers returned by Get<Type>ArrayElements.
-/// While wrapped, the object can be accessed via the `From` impl.
+/// While wrapped, the elements can be accessed as a slice via the `Deref` impl
+/// and `as_mut_slice`, or through a pointer via the `From` impl.
 ///
 /// AutoArray provides automatic array release through a call to appropriate
 /// Release<Type>ArrayElements when it goes out of scope.
 pub struct AutoArray<'a: 'b, 'b, T: TypeArray> {
     obj: JObject<'a>,
     ptr: NonNull<T>,
+    len: usize,
     mode: ReleaseMode,
     is_copy: bool,
     env: &'b JNIEnv<'a>,
@@ -74,9 +185,11 @@ pub struct AutoArray<'a: 'b, 'b, T: TypeArray> {
 
 impl<'a, 'b, T: TypeArray> AutoArray<'a, 'b, T> {
     pub(crate) fn new(env: &'b JNIEnv<'a>, obj: JObject<'a>, mode: ReleaseMode) -> Result<Self> {
+        let len = env.get_array_length(obj)? as usize;
         let mut is_copy: jboolean = 0xff;
         Ok(AutoArray {
             obj,
+            len,
             ptr: {
                 let ptr = T::get(env, obj, &mut is_copy)?;
                 NonNull::new(ptr).ok_or(Error::NullPtr("Non-null ptr expected"))?
@@ -92,6 +205,18 @@ impl<'a, 'b, T: TypeArray> AutoArray<'a, 'b, T> {
         self.ptr.as_ptr()
     }
 
+    /// Get the elements as a mutable slice.
+    ///
+    /// # Safety
+    ///
+    /// The JVM may hand out the same memory for every `AutoArray` (or
+    /// `AutoPrimitiveArray`) of an array, so no other one of the same array
+    /// may be used while the slice is alive, and the array must not be
+    /// accessed otherwise, e.g. by Java code on another thread.
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+        slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len)
+    }
+
     /// Commits the changes to the array, if it is a copy
     pub fn commit(&self) -> Result<()> {
         self.release_array_elements(sys::JNI_COMMIT)
@@ -116,7 +241,15 @@ impl<'a, 'b, T: TypeArray> AutoArray<'a, 'b, T> {
 
     /// Returns the array size
     pub fn size(&self) -> Result<jsize> {
-        self.env.get_array_length(*self.obj)
+        Ok(self.len as jsize)
+    }
+}
+
+impl<'a, 'b, T: TypeArray> ::std::ops::Deref for AutoArray<'a, 'b, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
     }
 }
 
@@ -2,53 +2,86 @@ use log::debug;
 
 use crate::sys::jsize;
 use crate::wrapper::objects::ReleaseMode;
-use crate::{errors::*, objects::JObject, JNIEnv};
+use crate::{
+    errors::*,
+    objects::{JObject, JPrimitiveArray, JString, TypeArray},
+    strings::JavaCharsCritical,
+    sys, JNIEnv,
+};
 use std::os::raw::c_void;
 use std::ptr::NonNull;
+use std::slice;
 
 /// Auto-release wrapper for pointer-based primitive arrays.
 ///
 /// This wrapper is used to wrap pointers returned by GetPrimitiveArrayCritical.
-/// While wrapped, the object can be accessed via the `From` impl.
+/// While wrapped, the elements can be accessed as a slice via the `Deref` and
+/// `DerefMut` impls.
 ///
 /// AutoPrimitiveArray provides automatic array release through a call to
 /// ReleasePrimitiveArrayCritical when it goes out of scope.
-pub struct AutoPrimitiveArray<'a: 'b, 'b> {
+///
+/// No JNI functions other than the ones of the `CriticalEnv` handle (see
+/// `split_env`) may be called while the array is held, which
+/// `JNIEnv#get_primitive_array_critical` requires of its callers.
+pub struct AutoPrimitiveArray<'a: 'b, 'b, T: TypeArray> {
     obj: JObject<'a>,
-    ptr: NonNull<c_void>,
+    ptr: NonNull<T>,
+    len: usize,
     mode: ReleaseMode,
     is_copy: bool,
     env: &'b JNIEnv<'a>,
 }
 
-impl<'a, 'b> AutoPrimitiveArray<'a, 'b> {
+impl<'a, 'b, T: TypeArray> AutoPrimitiveArray<'a, 'b, T> {
+    // The length must be queried before entering the critical region.
     pub(crate) fn new(
         env: &'b JNIEnv<'a>,
-        obj: JObject<'a>,
-        ptr: *mut c_void,
+        array: JPrimitiveArray<'a, T>,
+        len: jsize,
         mode: ReleaseMode,
-        is_copy: bool,
     ) -> Result<Self> {
+        let mut is_copy: sys::jboolean = 0xff;
+        // Even though this method may throw OoME, use `jni_unchecked`
+        // instead of `jni_non_null_call` to remove (a slight) overhead
+        // of exception checking. An error will still be detected as a `null`
+        // result below; and, as this method is unlikely to create a copy, an
+        // OoME is highly unlikely.
+        let ptr = jni_unchecked!(
+            env.get_native_interface(),
+            GetPrimitiveArrayCritical,
+            array.into_inner(),
+            &mut is_copy
+        );
         Ok(AutoPrimitiveArray {
-            obj,
-            ptr: NonNull::new(ptr).ok_or(Error::NullPtr("Non-null ptr expected"))?,
+            obj: array.into(),
+            ptr: NonNull::new(ptr as *mut T).ok_or(Error::NullPtr("Non-null ptr expected"))?,
+            len: len as usize,
             mode,
-            is_copy,
+            is_copy: is_copy == sys::JNI_TRUE,
             env,
         })
     }
 
     /// Get a reference to the wrapped pointer
-    pub fn as_ptr(&self) -> *mut c_void {
+    pub fn as_ptr(&self) -> *mut T {
         self.ptr.as_ptr()
     }
 
+    /// Get the elements as a mutable slice, along with a `CriticalEnv` handle
+    /// to call the JNI functions that are allowed while the array is held,
+    /// e.g. to get another critical array to copy elements to.
+    pub fn split_env(&mut self) -> (&mut [T], CriticalEnv<'a, '_>) {
+        let slice = unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) };
+        (slice, CriticalEnv::new(self.env))
+    }
+
     fn release_primitive_array_critical(&mut self, mode: i32) -> Result<()> {
         jni_unchecked!(
             self.env.get_native_interface(),
             ReleasePrimitiveArrayCritical,
             *self.obj,
-            self.ptr.as_mut(),
+            self.ptr.as_ptr() as *mut c_void,
             mode
         );
         Ok(())
@@ -69,11 +102,25 @@ impl<'a, 'b> AutoPrimitiveArray<'a, 'b> {
 
     /// Returns the array size
     pub fn size(&self) -> Result<jsize> {
-        self.env.get_array_length(*self.obj)
+        Ok(self.len as jsize)
     }
 }
 
-impl<'a, 'b> Drop for AutoPrimitiveArray<'a, 'b> {
+impl<'a, 'b, T: TypeArray> ::std::ops::Deref for AutoPrimitiveArray<'a, 'b, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<'a, 'b, T: TypeArray> ::std::ops::DerefMut for AutoPrimitiveArray<'a, 'b, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<'a, 'b, T: TypeArray> Drop for AutoPrimitiveArray<'a, 'b, T> {
     fn drop(&mut self) {
         let res = self.release_primitive_array_critical(self.mode as i32);
         match res {
@@ -83,8 +130,88 @@ impl<'a, 'b> Drop for AutoPrimitiveArray<'a, 'b> {
     }
 }
 
-impl<'a> From<&'a AutoPrimitiveArray<'a, '_>> for *mut c_void {
-    fn from(other: &'a AutoPrimitiveArray) -> *mut c_void {
+impl<'a, T: TypeArray> From<&'a AutoPrimitiveArray<'a, '_, T>> for *mut T {
+    fn from(other: &'a AutoPrimitiveArray<T>) -> *mut T {
         other.as_ptr()
     }
 }
+
+/// A restricted handle to the `JNIEnv` while a critical array or string is
+/// held, see `AutoPrimitiveArray#split_env` and `JavaCharsCritical#split_env`.
+///
+/// Between getting and releasing a critical array or string, the JNI
+/// specification only allows getting and releasing other critical arrays and
+/// strings, which is all this handle provides. As their lengths can't be
+/// queried then, they are given as `CriticalArray`s and `CriticalString`s,
+/// created beforehand.
+pub struct CriticalEnv<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> CriticalEnv<'a, 'b> {
+    pub(crate) fn new(env: &'b JNIEnv<'a>) -> Self {
+        CriticalEnv { env }
+    }
+
+    /// Get another critical array, see `JNIEnv#get_primitive_array_critical`.
+    ///
+    /// # Safety
+    ///
+    /// The array must not be held already, as its elements can be accessed
+    /// mutably through each `AutoPrimitiveArray`.
+    pub unsafe fn get_primitive_array_critical<U: TypeArray>(
+        &self,
+        array: CriticalArray<'a, U>,
+        mode: ReleaseMode,
+    ) -> Result<AutoPrimitiveArray<'a, 'b, U>> {
+        AutoPrimitiveArray::new(self.env, array.array, array.len, mode)
+    }
+
+    /// Get the contents of a string, see `JNIEnv#get_string_utf16_critical`.
+    pub fn get_string_utf16_critical(
+        &self,
+        string: CriticalString<'a>,
+    ) -> Result<JavaCharsCritical<'a, 'b>> {
+        JavaCharsCritical::new(self.env, string.string, string.len)
+    }
+}
+
+/// A primitive array along with its length, to get it with a `CriticalEnv`
+/// while another critical array or string is held.
+#[derive(Clone, Copy)]
+pub struct CriticalArray<'a, T: TypeArray> {
+    array: JPrimitiveArray<'a, T>,
+    len: jsize,
+}
+
+impl<'a, T: TypeArray> CriticalArray<'a, T> {
+    /// Queries the length of the array, which must be done before entering
+    /// the critical region.
+    ///
+    /// If the given array is `null`, an `Error::NullPtr` is returned.
+    pub fn new(env: &JNIEnv<'a>, array: JPrimitiveArray<'a, T>) -> Result<Self> {
+        non_null!(array, "CriticalArray array argument");
+        let len = env.get_array_length(array)?;
+        Ok(CriticalArray { array, len })
+    }
+}
+
+/// A string along with its length, to get its contents with a `CriticalEnv`
+/// while a critical array or string is held.
+#[derive(Clone, Copy)]
+pub struct CriticalString<'a> {
+    string: JString<'a>,
+    len: jsize,
+}
+
+impl<'a> CriticalString<'a> {
+    /// Queries the length of the string, which must be done before entering
+    /// the critical region.
+    ///
+    /// If the given string is `null`, an `Error::NullPtr` is returned.
+    pub fn new(env: &JNIEnv<'a>, string: JString<'a>) -> Result<Self> {
+        non_null!(string, "CriticalString string argument");
+        let len = env.get_string_length(string)?;
+        Ok(CriticalString { string, len })
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    errors::*,
+    java_class::{LazyClass, LazyId},
+    objects::{GlobalRef, JObject, JValue},
+    signature::{Primitive, ReturnType},
+    sys::{jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+    JNIEnv,
+};
+
+/// Rust primitive types that can be boxed into, and unboxed from, their
+/// `java.lang` wrapper class, e.g. `jint` and `java.lang.Integer`, see
+/// `JNIEnv#box_value` and `JNIEnv#unbox_value`.
+///
+/// The wrapper classes are looked up once, and kept alive with a `GlobalRef`,
+/// and the IDs of their `valueOf` and `<type>Value` methods are looked up the
+/// first time they are used and reused afterwards.
+pub trait Boxable: Sized {
+    /// The name of the wrapper class, e.g. `java/lang/Integer`.
+    const CLASS_NAME: &'static str;
+
+    /// Returns the wrapper class, which is looked up once.
+    fn class(env: &JNIEnv) -> Result<&'static GlobalRef>;
+
+    /// Boxes the value with `valueOf`, see `JNIEnv#box_value`.
+    fn to_boxed<'a>(self, env: &JNIEnv<'a>) -> Result<JObject<'a>>;
+
+    /// Unboxes the value of a wrapper object, see `JNIEnv#unbox_value`.
+    fn from_boxed<'a>(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self>;
+}
+
+macro_rules! boxable {
+    (
+        $ty:ty,
+        $class:literal,
+        $binary_name:literal,
+        $value_of_sig:literal,
+        $unbox:literal,
+        $unbox_sig:literal,
+        $primitive:ident,
+        $unwrap:ident
+    ) => {
+        impl Boxable for $ty {
+            const CLASS_NAME: &'static str = $class;
+
+            fn class(env: &JNIEnv) -> Result<&'static GlobalRef> {
+                static CLASS: LazyClass = LazyClass::new();
+                CLASS.get(env, $class)
+            }
+
+            fn to_boxed<'a>(self, env: &JNIEnv<'a>) -> Result<JObject<'a>> {
+                static ID: LazyId = LazyId::new();
+                let class = Self::class(env)?;
+                let args = [JValue::from(self)];
+                let id = ID.static_method(
+                    env,
+                    class,
+                    "valueOf",
+                    $value_of_sig,
+                    &args,
+                    ReturnType::Object,
+                )?;
+                env.call_static_method_unchecked(
+                    class,
+                    id,
+                    ReturnType::Object,
+                    &args.map(JValue::to_jni),
+                )?
+                .l()
+            }
+
+            fn from_boxed<'a>(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<Self> {
+                static ID: LazyId = LazyId::new();
+                non_null!(obj, concat!("unboxing ", $binary_name));
+                let class = Self::class(env)?;
+                if !env.is_instance_of(obj, class)? {
+                    return Err(Error::WrongObjectType($binary_name));
+                }
+                let ret = ReturnType::Primitive(Primitive::$primitive);
+                let id = ID.method(env, class, $unbox, $unbox_sig, &[], ret.clone())?;
+                env.call_method_unchecked(obj, id, ret, &[])?.$unwrap()
+            }
+        }
+    };
+}
+
+boxable!(
+    bool,
+    "java/lang/Boolean",
+    "java.lang.Boolean",
+    "(Z)Ljava/lang/Boolean;",
+    "booleanValue",
+    "()Z",
+    Boolean,
+    z
+);
+boxable!(
+    jbyte,
+    "java/lang/Byte",
+    "java.lang.Byte",
+    "(B)Ljava/lang/Byte;",
+    "byteValue",
+    "()B",
+    Byte,
+    b
+);
+boxable!(
+    jchar,
+    "java/lang/Character",
+    "java.lang.Character",
+    "(C)Ljava/lang/Character;",
+    "charValue",
+    "()C",
+    Char,
+    c
+);
+boxable!(
+    jshort,
+    "java/lang/Short",
+    "java.lang.Short",
+    "(S)Ljava/lang/Short;",
+    "shortValue",
+    "()S",
+    Short,
+    s
+);
+boxable!(
+    jint,
+    "java/lang/Integer",
+    "java.lang.Integer",
+    "(I)Ljava/lang/Integer;",
+    "intValue",
+    "()I",
+    Int,
+    i
+);
+boxable!(
+    jlong,
+    "java/lang/Long",
+    "java.lang.Long",
+    "(J)Ljava/lang/Long;",
+    "longValue",
+    "()J",
+    Long,
+    j
+);
+boxable!(
+    jfloat,
+    "java/lang/Float",
+    "java.lang.Float",
+    "(F)Ljava/lang/Float;",
+    "floatValue",
+    "()F",
+    Float,
+    f
+);
+boxable!(
+    jdouble,
+    "java/lang/Double",
+    "java.lang.Double",
+    "(D)Ljava/lang/Double;",
+    "doubleValue",
+    "()D",
+    Double,
+    d
+);
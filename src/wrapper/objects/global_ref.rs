@@ -2,7 +2,11 @@ use std::{convert::From, sync::Arc};
 
 use log::{debug, warn};
 
-use crate::{errors::Result, objects::JObject, sys, JNIEnv, JavaVM};
+use crate::{
+    errors::Result,
+    objects::{JObject, JObjectRefType},
+    sys, JNIEnv, JavaVM,
+};
 
 /// A global JVM reference. These are "pinned" by the garbage collector and are
 /// guaranteed to not get collected until released. Thus, this is allowed to
@@ -84,6 +88,7 @@ impl GlobalRefGuard {
 impl Drop for GlobalRefGuard {
     fn drop(&mut self) {
         fn drop_impl(env: &JNIEnv, global_ref: JObject) -> Result<()> {
+            env.debug_assert_ref_type(global_ref, JObjectRefType::Global, "GlobalRef drop");
             let internal = env.get_native_interface();
             // This method is safe to call in case of pending exceptions (see chapter 2 of the spec)
             jni_unchecked!(internal, DeleteGlobalRef, global_ref.into_inner());
@@ -1,4 +1,12 @@
-use crate::{objects::JObject, sys::jobject};
+use std::{convert::TryFrom, io, ptr};
+
+use crate::{
+    errors::*,
+    objects::{JMethodID, JObject, JValue},
+    signature::{Primitive, ReturnType},
+    sys::{jint, jobject},
+    JNIEnv,
+};
 
 /// Lifetime'd representation of a `jobject` that is an instance of the
 /// ByteBuffer Java class. Just a `JObject` wrapped in a new class.
@@ -37,3 +45,272 @@ impl<'a> std::default::Default for JByteBuffer<'a> {
         JByteBuffer(JObject::null())
     }
 }
+
+/// Wrapper for a `java.nio.ByteBuffer`, to read and write its bytes through
+/// slices, starting at the position of the buffer and up to its limit, and
+/// advancing its position. Implements `std::io::Read`, `Write` and `Seek`,
+/// where seeking moves the position within the limit.
+///
+/// The bytes of direct buffers are accessed through their address, and those
+/// of heap buffers (or direct buffers whose address isn't available) are
+/// copied via `get(byte[])` and `put(byte[])`. Writing to a read-only buffer
+/// always goes through `put`, which throws a `ReadOnlyBufferException`.
+///
+/// Looks up the class and method ids on creation rather than for every method
+/// call.
+pub struct JByteBufferStream<'a: 'b, 'b> {
+    internal: JByteBuffer<'a>,
+    direct: Option<DirectMemory>,
+    read_only: bool,
+    position: JMethodID,
+    set_position: JMethodID,
+    limit: JMethodID,
+    set_limit: JMethodID,
+    capacity: JMethodID,
+    get: JMethodID,
+    put: JMethodID,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JByteBufferStream<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JByteBufferStream<'a, 'b>> for JByteBuffer<'a> {
+    fn from(other: JByteBufferStream<'a, 'b>) -> JByteBuffer<'a> {
+        other.internal
+    }
+}
+
+impl<'a: 'b, 'b> JByteBufferStream<'a, 'b> {
+    /// Create a stream from the environment and a buffer, see
+    /// `JNIEnv#get_byte_buffer_stream`. Returns `Error::WrongObjectType` if
+    /// the object isn't a `java.nio.ByteBuffer`.
+    pub fn from_env(env: &'b JNIEnv<'a>, buf: JByteBuffer<'a>) -> Result<Self> {
+        non_null!(buf, "JByteBufferStream::from_env buf argument");
+        let class = env.auto_local(env.find_class("java/nio/ByteBuffer")?);
+        if !env.is_instance_of(buf, &class)? {
+            return Err(Error::WrongObjectType("java.nio.ByteBuffer"));
+        }
+
+        // The `Buffer` methods are looked up on `Buffer`, as their return
+        // type is only overridden in `ByteBuffer` since Java 9.
+        let buffer_class = env.auto_local(env.find_class("java/nio/Buffer")?);
+        let position = env.get_method_id(&buffer_class, "position", "()I")?;
+        let set_position = env.get_method_id(&buffer_class, "position", "(I)Ljava/nio/Buffer;")?;
+        let limit = env.get_method_id(&buffer_class, "limit", "()I")?;
+        let set_limit = env.get_method_id(&buffer_class, "limit", "(I)Ljava/nio/Buffer;")?;
+        let capacity = env.get_method_id(&buffer_class, "capacity", "()I")?;
+        let get = env.get_method_id(&class, "get", "([BII)Ljava/nio/ByteBuffer;")?;
+        let put = env.get_method_id(&class, "put", "([BII)Ljava/nio/ByteBuffer;")?;
+        let read_only = env.call_method(buf, "isReadOnly", "()Z", &[])?.z()?;
+
+        let direct = if read_only {
+            None
+        } else {
+            match env.get_direct_buffer_memory(buf) {
+                Ok((ptr, capacity)) => Some(DirectMemory { ptr, capacity }),
+                Err(Error::NullPtr(_)) => None,
+                Err(e) => return Err(e),
+            }
+        };
+
+        Ok(JByteBufferStream {
+            internal: buf,
+            direct,
+            read_only,
+            position,
+            set_position,
+            limit,
+            set_limit,
+            capacity,
+            get,
+            put,
+            env,
+        })
+    }
+
+    fn call_int(&self, id: JMethodID) -> Result<jint> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                id,
+                ReturnType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()
+    }
+
+    // Calls a method returning the buffer itself, deleting the returned
+    // local reference.
+    fn call_chained(&self, id: JMethodID, args: &[JValue]) -> Result<()> {
+        let args: Vec<_> = args.iter().map(|arg| arg.to_jni()).collect();
+        let this = self
+            .env
+            .call_method_unchecked(self.internal, id, ReturnType::Object, &args)?
+            .l()?;
+        self.env.delete_local_ref(this)
+    }
+
+    /// Returns the position of the buffer, the index of the next byte to be
+    /// read or written.
+    pub fn position(&self) -> Result<jint> {
+        self.call_int(self.position)
+    }
+
+    /// Sets the position of the buffer, which must not be larger than its
+    /// limit.
+    pub fn set_position(&self, position: jint) -> Result<()> {
+        self.call_chained(self.set_position, &[position.into()])
+    }
+
+    /// Returns the limit of the buffer, the index of the first byte that
+    /// shouldn't be read or written.
+    pub fn limit(&self) -> Result<jint> {
+        self.call_int(self.limit)
+    }
+
+    /// Sets the limit of the buffer, which must not be larger than its
+    /// capacity. The position is moved to the new limit if it is larger.
+    pub fn set_limit(&self, limit: jint) -> Result<()> {
+        self.call_chained(self.set_limit, &[limit.into()])
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> Result<jint> {
+        self.call_int(self.capacity)
+    }
+
+    /// Returns the number of bytes between the position and the limit.
+    pub fn remaining(&self) -> Result<jint> {
+        Ok(self.limit()? - self.position()?)
+    }
+
+    /// Whether the buffer is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Reads as many bytes as are remaining, or fit in `buf`, into `buf`, and
+    /// advances the position past them. Returns the number of bytes read,
+    /// which is 0 once the position has reached the limit.
+    pub fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let position = self.position()?;
+        let len = (self.limit()? - position).max(0).min(clamp_len(buf.len()));
+        if len == 0 {
+            return Ok(0);
+        }
+        let n = len as usize;
+
+        if let Some(direct) = self.direct {
+            let src = direct.range(position, len)?;
+            unsafe { ptr::copy_nonoverlapping(src, buf.as_mut_ptr(), n) };
+            self.set_position(position + len)?;
+        } else {
+            let array = self.env.auto_local(self.env.new_byte_array(len)?);
+            self.call_chained(self.get, &[array.as_obj().into(), 0.into(), len.into()])?;
+            let bytes = self.env.convert_byte_array(array.as_obj().into())?;
+            buf[..n].copy_from_slice(&bytes);
+        }
+        Ok(n)
+    }
+
+    /// Writes as many bytes of `buf` as there is room for before the limit,
+    /// and advances the position past them. Returns the number of bytes
+    /// written, which is 0 once the position has reached the limit.
+    pub fn write_bytes(&mut self, buf: &[u8]) -> Result<usize> {
+        let position = self.position()?;
+        let len = (self.limit()? - position).max(0).min(clamp_len(buf.len()));
+        if len == 0 && !self.read_only {
+            return Ok(0);
+        }
+        let n = len as usize;
+
+        if let Some(direct) = self.direct {
+            let dst = direct.range(position, len)?;
+            unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), dst, n) };
+            self.set_position(position + len)?;
+        } else {
+            let array = self
+                .env
+                .auto_local(self.env.byte_array_from_slice(&buf[..n])?);
+            self.call_chained(self.put, &[array.as_obj().into(), 0.into(), len.into()])?;
+        }
+        Ok(n)
+    }
+}
+
+// The memory of a writable direct buffer. It's only copied from and to, and
+// never borrowed as a slice, as Java code or other streams may access it at
+// any time too.
+#[derive(Clone, Copy)]
+struct DirectMemory {
+    ptr: *mut u8,
+    capacity: usize,
+}
+
+impl DirectMemory {
+    // The address of `len` bytes at `start`, checked against the capacity,
+    // as the position and limit come from the buffer.
+    fn range(self, start: jint, len: jint) -> Result<*mut u8> {
+        let end = start.checked_add(len).filter(|_| start >= 0 && len >= 0);
+        match end {
+            Some(end) if end as usize <= self.capacity => {
+                Ok(unsafe { self.ptr.add(start as usize) })
+            }
+            _ => Err(Error::IndexOutOfBounds(start)),
+        }
+    }
+}
+
+fn clamp_len(len: usize) -> jint {
+    len.min(jint::MAX as usize) as jint
+}
+
+fn to_io_error(e: Error) -> io::Error {
+    io::Error::other(e)
+}
+
+impl<'a: 'b, 'b> io::Read for JByteBufferStream<'a, 'b> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_bytes(buf).map_err(to_io_error)
+    }
+}
+
+impl<'a: 'b, 'b> io::Write for JByteBufferStream<'a, 'b> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_bytes(buf).map_err(to_io_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a: 'b, 'b> io::Seek for JByteBufferStream<'a, 'b> {
+    /// Moves the position of the buffer. Seeking before the start or past
+    /// the limit of the buffer is an `InvalidInput` error.
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            io::SeekFrom::Start(offset) => (0, i64::try_from(offset).unwrap_or(i64::MAX)),
+            io::SeekFrom::End(offset) => (self.limit().map_err(to_io_error)?, offset),
+            io::SeekFrom::Current(offset) => (self.position().map_err(to_io_error)?, offset),
+        };
+        let limit = self.limit().map_err(to_io_error)?;
+        let position = i64::from(base)
+            .checked_add(offset)
+            .filter(|position| (0..=i64::from(limit)).contains(position))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "seek position outside of the buffer limit",
+                )
+            })?;
+        self.set_position(position as jint).map_err(to_io_error)?;
+        Ok(position as u64)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    errors::*,
+    objects::{JIterator, JMethodID, JObject, JValue},
+    signature::{Primitive, ReturnType},
+    sys::jint,
+    JNIEnv,
+};
+
+/// Wrapper for JObjects that implement `java/util/Collection`. Provides
+/// methods to add, remove and look for elements, and a way to iterate over
+/// them.
+///
+/// Looks up the class and method ids on creation rather than for every method
+/// call.
+pub struct JCollection<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    add: JMethodID,
+    remove: JMethodID,
+    contains: JMethodID,
+    size: JMethodID,
+    clear: JMethodID,
+    iterator: JMethodID,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JCollection<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JCollection<'a, 'b>> for JObject<'a> {
+    fn from(other: JCollection<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+impl<'a: 'b, 'b> JCollection<'a, 'b> {
+    /// Create a collection from the environment and an object. This looks up
+    /// the necessary class and method ids to call all of the methods on it so
+    /// that exra work doesn't need to be done on every method call.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<JCollection<'a, 'b>> {
+        Self::with_class(env, obj, "java/util/Collection")
+    }
+
+    // Looks up the methods with the given class (or interface), which must
+    // be a `Collection`.
+    pub(crate) fn with_class(
+        env: &'b JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: &str,
+    ) -> Result<JCollection<'a, 'b>> {
+        let class = env.auto_local(env.find_class(class)?);
+
+        let add = env.get_method_id(&class, "add", "(Ljava/lang/Object;)Z")?;
+        let remove = env.get_method_id(&class, "remove", "(Ljava/lang/Object;)Z")?;
+        let contains = env.get_method_id(&class, "contains", "(Ljava/lang/Object;)Z")?;
+        let size = env.get_method_id(&class, "size", "()I")?;
+        let clear = env.get_method_id(&class, "clear", "()V")?;
+        let iterator = env.get_method_id(&class, "iterator", "()Ljava/util/Iterator;")?;
+
+        Ok(JCollection {
+            internal: obj,
+            add,
+            remove,
+            contains,
+            size,
+            clear,
+            iterator,
+            env,
+        })
+    }
+
+    /// Add an element to the collection. Returns `false` if the collection
+    /// didn't change, e.g. if it is a set that already contains the element.
+    pub fn add(&self, value: JObject<'a>) -> Result<bool> {
+        self.call_with_object(self.add, value)
+    }
+
+    /// Add all the given objects to the collection, e.g. the elements of a
+    /// `Vec<JObject>` or a `&[GlobalRef]`. Returns `true` if the collection
+    /// changed.
+    pub fn add_all<'o, I>(&self, objects: I) -> Result<bool>
+    where
+        I: IntoIterator,
+        I::Item: Into<JObject<'o>>,
+    {
+        let mut changed = false;
+        for obj in objects {
+            changed |= self
+                .env
+                .call_method_unchecked(
+                    self.internal,
+                    self.add,
+                    ReturnType::Primitive(Primitive::Boolean),
+                    &[JValue::from(obj.into()).to_jni()],
+                )?
+                .z()?;
+        }
+        Ok(changed)
+    }
+
+    /// Remove an element from the collection, as compared with
+    /// `Object#equals`. Returns `false` if the collection didn't contain it.
+    pub fn remove(&self, value: JObject<'a>) -> Result<bool> {
+        self.call_with_object(self.remove, value)
+    }
+
+    /// Check whether the collection contains an element, as compared with
+    /// `Object#equals`.
+    pub fn contains(&self, value: JObject<'a>) -> Result<bool> {
+        self.call_with_object(self.contains, value)
+    }
+
+    fn call_with_object(&self, method: JMethodID, value: JObject<'a>) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                method,
+                ReturnType::Primitive(Primitive::Boolean),
+                &[JValue::from(value).to_jni()],
+            )?
+            .z()
+    }
+
+    /// Get the size of the collection
+    pub fn size(&self) -> Result<jint> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.size,
+                ReturnType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()
+    }
+
+    /// Check whether the collection is empty
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.size()? == 0)
+    }
+
+    /// Remove all the elements from the collection
+    pub fn clear(&self) -> Result<()> {
+        self.env.call_method_unchecked(
+            self.internal,
+            self.clear,
+            ReturnType::Primitive(Primitive::Void),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Get an iterator over the elements of the collection, see `JIterator`.
+    pub fn iter(&self) -> Result<JIterator<'a, 'b>> {
+        let iter = self
+            .env
+            .call_method_unchecked(self.internal, self.iterator, ReturnType::Object, &[])?
+            .l()?;
+        JIterator::from_local(self.env, iter)
+    }
+}
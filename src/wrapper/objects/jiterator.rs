@@ -0,0 +1,189 @@
+use std::mem;
+
+use log::debug;
+
+use crate::{
+    errors::*,
+    objects::{AutoLocal, JMethodID, JObject},
+    signature::{Primitive, ReturnType},
+    JNIEnv,
+};
+
+/// Wrapper for JObjects that implement `java/util/Iterator`. Iterates over the
+/// elements as `AutoLocal`s, so that the local reference to each element is
+/// deleted once it is dropped, unless it is kept with `AutoLocal#forget`.
+/// The iteration ends early if a method call fails, leaving the Java exception
+/// pending; use `next_elem` to handle errors.
+///
+/// Looks up the class and method ids on creation rather than for every method
+/// call.
+pub struct JIterator<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    has_next: JMethodID,
+    next: JMethodID,
+    // Whether the iterator was created by the wrapper, in which case its local
+    // reference is deleted on drop.
+    owned: bool,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JIterator<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JIterator<'a, 'b>> for JObject<'a> {
+    fn from(other: JIterator<'a, 'b>) -> JObject<'a> {
+        let obj = other.internal;
+        mem::forget(other);
+        obj
+    }
+}
+
+impl<'a: 'b, 'b> Drop for JIterator<'a, 'b> {
+    fn drop(&mut self) {
+        if self.owned {
+            if let Err(e) = self.env.delete_local_ref(self.internal) {
+                debug!("error dropping iterator local ref: {:#?}", e);
+            }
+        }
+    }
+}
+
+impl<'a: 'b, 'b> JIterator<'a, 'b> {
+    /// Create an iterator from the environment and an object. This looks up
+    /// the necessary class and method ids to call all of the methods on it so
+    /// that exra work doesn't need to be done on every method call.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<JIterator<'a, 'b>> {
+        Self::new(env, obj, false)
+    }
+
+    // Wraps a local reference to an iterator, which is deleted when the
+    // wrapper is dropped, or if creating it fails.
+    pub(crate) fn from_local(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<JIterator<'a, 'b>> {
+        let obj = env.auto_local(obj);
+        let iter = Self::new(env, obj.as_obj(), true)?;
+        obj.forget();
+        Ok(iter)
+    }
+
+    fn new(env: &'b JNIEnv<'a>, obj: JObject<'a>, owned: bool) -> Result<JIterator<'a, 'b>> {
+        let class = env.auto_local(env.find_class("java/util/Iterator")?);
+
+        let has_next = env.get_method_id(&class, "hasNext", "()Z")?;
+        let next = env.get_method_id(&class, "next", "()Ljava/lang/Object;")?;
+
+        Ok(JIterator {
+            internal: obj,
+            has_next,
+            next,
+            owned,
+            env,
+        })
+    }
+
+    /// Check whether the iteration has more elements
+    pub fn has_next(&self) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.has_next,
+                ReturnType::Primitive(Primitive::Boolean),
+                &[],
+            )?
+            .z()
+    }
+
+    /// Get the next element. Returns `None` at the end of the iteration.
+    ///
+    /// Unlike with `Iterator#next`, the local reference to the element isn't
+    /// deleted automatically, and errors are returned rather than ending the
+    /// iteration.
+    pub fn next_elem(&self) -> Result<Option<JObject<'a>>> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+        let elem = self
+            .env
+            .call_method_unchecked(self.internal, self.next, ReturnType::Object, &[])?
+            .l()?;
+        Ok(Some(elem))
+    }
+}
+
+impl<'a: 'b, 'b> Iterator for JIterator<'a, 'b> {
+    type Item = AutoLocal<'a, 'b>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_elem() {
+            Ok(Some(elem)) => Some(self.env.auto_local(elem)),
+            _ => None,
+        }
+    }
+}
+
+/// Wrapper for JObjects that can be iterated over: instances of
+/// `java/lang/Iterable` (including all the collections), streams, or anything
+/// else with an `iterator()` method returning a `java/util/Iterator`, as well
+/// as iterators themselves.
+pub struct JIterable<'a: 'b, 'b> {
+    internal: JObject<'a>,
+    // `None` if the object is an iterator itself.
+    iterator: Option<JMethodID>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JIterable<'a, 'b> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JIterable<'a, 'b>> for JObject<'a> {
+    fn from(other: JIterable<'a, 'b>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+impl<'a: 'b, 'b> JIterable<'a, 'b> {
+    /// Create an iterable from the environment and an object. This looks up
+    /// the `iterator()` method of the object's class, and fails if there is
+    /// none.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<JIterable<'a, 'b>> {
+        let iterator = if env.is_instance_of(obj, "java/util/Iterator")? {
+            None
+        } else {
+            let class = env.auto_local(env.get_object_class(obj)?);
+            Some(env.get_method_id(&class, "iterator", "()Ljava/util/Iterator;")?)
+        };
+
+        Ok(JIterable {
+            internal: obj,
+            iterator,
+            env,
+        })
+    }
+
+    /// Get an iterator over the elements. The local reference to the Java
+    /// iterator is deleted when it is dropped.
+    ///
+    /// Note that if the object is an iterator itself, this returns a wrapper
+    /// of that same iterator, which can only be iterated over once.
+    pub fn iter(&self) -> Result<JIterator<'a, 'b>> {
+        match self.iterator {
+            Some(iterator) => {
+                let iter = self
+                    .env
+                    .call_method_unchecked(self.internal, iterator, ReturnType::Object, &[])?
+                    .l()?;
+                JIterator::from_local(self.env, iter)
+            }
+            None => JIterator::new(self.env, self.internal, false),
+        }
+    }
+}
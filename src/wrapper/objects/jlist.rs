@@ -1,24 +1,36 @@
 use crate::{
     errors::*,
-    objects::{JMethodID, JObject, JValue},
+    objects::{AutoLocal, GlobalRef, JMethodID, JObject, JValue},
     signature::{Primitive, ReturnType},
     sys::jint,
     JNIEnv,
 };
 
 /// Wrapper for JObjects that implement `java/util/List`. Provides methods to get,
-/// add, and remove elements.
+/// add, replace, search and remove elements, and a way to iterate over them.
 ///
 /// Looks up the class and method ids on creation rather than for every method
 /// call.
 pub struct JList<'a: 'b, 'b> {
     internal: JObject<'a>,
+    ids: JListIds,
+    env: &'b JNIEnv<'a>,
+}
+
+// The method ids, shared with the lists returned by `sub_list`.
+#[derive(Clone, Copy)]
+struct JListIds {
     get: JMethodID,
     add: JMethodID,
     add_idx: JMethodID,
+    set: JMethodID,
     remove: JMethodID,
     size: JMethodID,
-    env: &'b JNIEnv<'a>,
+    clear: JMethodID,
+    contains: JMethodID,
+    index_of: JMethodID,
+    last_index_of: JMethodID,
+    sub_list: JMethodID,
 }
 
 impl<'a: 'b, 'b> ::std::ops::Deref for JList<'a, 'b> {
@@ -45,26 +57,61 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
         let get = env.get_method_id(&class, "get", "(I)Ljava/lang/Object;")?;
         let add = env.get_method_id(&class, "add", "(Ljava/lang/Object;)Z")?;
         let add_idx = env.get_method_id(&class, "add", "(ILjava/lang/Object;)V")?;
+        let set = env.get_method_id(&class, "set", "(ILjava/lang/Object;)Ljava/lang/Object;")?;
         let remove = env.get_method_id(&class, "remove", "(I)Ljava/lang/Object;")?;
         let size = env.get_method_id(&class, "size", "()I")?;
+        let clear = env.get_method_id(&class, "clear", "()V")?;
+        let contains = env.get_method_id(&class, "contains", "(Ljava/lang/Object;)Z")?;
+        let index_of = env.get_method_id(&class, "indexOf", "(Ljava/lang/Object;)I")?;
+        let last_index_of = env.get_method_id(&class, "lastIndexOf", "(Ljava/lang/Object;)I")?;
+        let sub_list = env.get_method_id(&class, "subList", "(II)Ljava/util/List;")?;
 
         Ok(JList {
             internal: obj,
-            get,
-            add,
-            add_idx,
-            remove,
-            size,
+            ids: JListIds {
+                get,
+                add,
+                add_idx,
+                set,
+                remove,
+                size,
+                clear,
+                contains,
+                index_of,
+                last_index_of,
+                sub_list,
+            },
             env,
         })
     }
 
+    /// Create a new, empty `java.util.ArrayList` with the given initial
+    /// capacity.
+    pub fn new_array_list(env: &'b JNIEnv<'a>, capacity: jint) -> Result<JList<'a, 'b>> {
+        let obj = env.new_object("java/util/ArrayList", "(I)V", &[JValue::from(capacity)])?;
+        JList::from_env(env, obj)
+    }
+
+    /// Create a new `java.util.ArrayList` holding the given objects, e.g. the
+    /// elements of a `Vec<JObject>` or a `&[GlobalRef]`.
+    pub fn from_objects<'o, I>(env: &'b JNIEnv<'a>, objects: I) -> Result<JList<'a, 'b>>
+    where
+        I: IntoIterator,
+        I::Item: Into<JObject<'o>>,
+    {
+        let objects = objects.into_iter();
+        let capacity = objects.size_hint().0.min(jint::MAX as usize) as jint;
+        let list = JList::new_array_list(env, capacity)?;
+        list.add_all(objects)?;
+        Ok(list)
+    }
+
     /// Look up the value for a key. Returns `Some` if it's found and `None` if
     /// a null pointer would be returned.
     pub fn get(&self, idx: jint) -> Result<Option<JObject<'a>>> {
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.get,
+            self.ids.get,
             ReturnType::Object,
             &[JValue::from(idx).to_jni()],
         );
@@ -82,7 +129,7 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
     pub fn add(&self, value: JObject<'a>) -> Result<()> {
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.add,
+            self.ids.add,
             ReturnType::Primitive(Primitive::Boolean),
             &[JValue::from(value).to_jni()],
         );
@@ -95,7 +142,7 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
     pub fn insert(&self, idx: jint, value: JObject<'a>) -> Result<()> {
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.add_idx,
+            self.ids.add_idx,
             ReturnType::Primitive(Primitive::Void),
             &[JValue::from(idx).to_jni(), JValue::from(value).to_jni()],
         );
@@ -104,11 +151,42 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
         Ok(())
     }
 
+    /// Append all the given objects to the list, e.g. the elements of a
+    /// `Vec<JObject>` or a `&[GlobalRef]`.
+    pub fn add_all<'o, I>(&self, objects: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<JObject<'o>>,
+    {
+        for obj in objects {
+            self.env.call_method_unchecked(
+                self.internal,
+                self.ids.add,
+                ReturnType::Primitive(Primitive::Boolean),
+                &[JValue::from(obj.into()).to_jni()],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replace the element at a specific index. Returns the element
+    /// previously at that index.
+    pub fn set(&self, idx: jint, value: JObject<'a>) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.ids.set,
+                ReturnType::Object,
+                &[JValue::from(idx).to_jni(), JValue::from(value).to_jni()],
+            )?
+            .l()
+    }
+
     /// Remove an element from the list by index
     pub fn remove(&self, idx: jint) -> Result<Option<JObject<'a>>> {
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.remove,
+            self.ids.remove,
             ReturnType::Object,
             &[JValue::from(idx).to_jni()],
         );
@@ -126,7 +204,7 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
     pub fn size(&self) -> Result<jint> {
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.size,
+            self.ids.size,
             ReturnType::Primitive(Primitive::Int),
             &[],
         );
@@ -134,6 +212,113 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
         result.and_then(|v| v.i())
     }
 
+    /// Check whether the list is empty
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.size()? == 0)
+    }
+
+    /// Remove all the elements from the list
+    pub fn clear(&self) -> Result<()> {
+        self.env.call_method_unchecked(
+            self.internal,
+            self.ids.clear,
+            ReturnType::Primitive(Primitive::Void),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Check whether the list contains an element, as compared with
+    /// `Object#equals`.
+    pub fn contains(&self, value: JObject<'a>) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.ids.contains,
+                ReturnType::Primitive(Primitive::Boolean),
+                &[JValue::from(value).to_jni()],
+            )?
+            .z()
+    }
+
+    /// Get the index of the first occurrence of an element, as compared with
+    /// `Object#equals`. Returns `None` if the list doesn't contain it.
+    pub fn index_of(&self, value: JObject<'a>) -> Result<Option<jint>> {
+        self.find_index(self.ids.index_of, value)
+    }
+
+    /// Get the index of the last occurrence of an element, as compared with
+    /// `Object#equals`. Returns `None` if the list doesn't contain it.
+    pub fn last_index_of(&self, value: JObject<'a>) -> Result<Option<jint>> {
+        self.find_index(self.ids.last_index_of, value)
+    }
+
+    fn find_index(&self, method: JMethodID, value: JObject<'a>) -> Result<Option<jint>> {
+        let idx = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                method,
+                ReturnType::Primitive(Primitive::Int),
+                &[JValue::from(value).to_jni()],
+            )?
+            .i()?;
+        Ok(if idx < 0 { None } else { Some(idx) })
+    }
+
+    /// Get a view of the portion of the list between `from` (inclusive) and
+    /// `to` (exclusive). Changes to the sub-list are reflected in this list.
+    pub fn sub_list(&self, from: jint, to: jint) -> Result<JList<'a, 'b>> {
+        let obj = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                self.ids.sub_list,
+                ReturnType::Object,
+                &[JValue::from(from).to_jni(), JValue::from(to).to_jni()],
+            )?
+            .l()?;
+        Ok(JList {
+            internal: obj,
+            ids: self.ids,
+            env: self.env,
+        })
+    }
+
+    /// Get the elements of the list as local references.
+    pub fn to_vec(&self) -> Result<Vec<JObject<'a>>> {
+        let size = self.size()?;
+        let mut vec = Vec::with_capacity(size as usize);
+        for idx in 0..size {
+            vec.push(self.get_elem(idx)?);
+        }
+        Ok(vec)
+    }
+
+    /// Get the elements of the list as global references. The local reference
+    /// to each element is deleted as soon as its global reference is created,
+    /// so that long lists don't overflow the local reference table.
+    pub fn to_global_vec(&self) -> Result<Vec<GlobalRef>> {
+        let size = self.size()?;
+        let mut vec = Vec::with_capacity(size as usize);
+        for idx in 0..size {
+            let elem = self.env.auto_local(self.get_elem(idx)?);
+            vec.push(self.env.new_global_ref(&elem)?);
+        }
+        Ok(vec)
+    }
+
+    fn get_elem(&self, idx: jint) -> Result<JObject<'a>> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.ids.get,
+                ReturnType::Object,
+                &[JValue::from(idx).to_jni()],
+            )?
+            .l()
+    }
+
     /// Pop the last element from the list
     ///
     /// Note that this calls `size()` to determine the last index.
@@ -145,7 +330,7 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
 
         let result = self.env.call_method_unchecked(
             self.internal,
-            self.remove,
+            self.ids.remove,
             ReturnType::Object,
             &[JValue::from(size - 1).to_jni()],
         );
@@ -159,44 +344,70 @@ impl<'a: 'b, 'b> JList<'a, 'b> {
         }
     }
 
-    /// Get key/value iterator for the map. This is done by getting the
-    /// `EntrySet` from java and iterating over it.
+    /// Get an iterator over the elements of the list, from the first to the
+    /// last, or the other way around with `rev()`.
+    ///
+    /// The elements are looked up by index, within the size the list had when
+    /// the iterator was created, so the iterator has an exact size. The local
+    /// reference to each element is deleted when it is dropped, so that
+    /// iterating over a long list doesn't overflow the local reference table.
+    ///
+    /// Looking up an element may fail, e.g. if the list was shortened, in
+    /// which case the error is yielded, with the Java exception, if any, left
+    /// pending as for any other method. It should be handled before using the
+    /// `JNIEnv` again, so stop iterating on errors, e.g. by collecting into a
+    /// `Result`.
     pub fn iter(&self) -> Result<JListIter<'a, 'b, '_>> {
         Ok(JListIter {
             list: self,
             current: 0,
-            size: self.size()?,
+            end: self.size()?,
         })
     }
 }
 
-/// An iterator over the keys and values in a map.
-///
-/// TODO: make the iterator implementation for java iterators its own thing
-/// and generic enough to use elsewhere.
+/// A double-ended iterator over the elements of a list, with an exact size,
+/// see `JList#iter`.
 pub struct JListIter<'a: 'b, 'b: 'c, 'c> {
     list: &'c JList<'a, 'b>,
     current: jint,
-    size: jint,
+    end: jint,
+}
+
+impl<'a: 'b, 'b: 'c, 'c> JListIter<'a, 'b, 'c> {
+    fn get(&self, idx: jint) -> Result<AutoLocal<'a, 'b>> {
+        let elem = self.list.get_elem(idx)?;
+        Ok(self.list.env.auto_local(elem))
+    }
 }
 
 impl<'a: 'b, 'b: 'c, 'c> Iterator for JListIter<'a, 'b, 'c> {
-    type Item = JObject<'a>;
+    type Item = Result<AutoLocal<'a, 'b>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current == self.size {
+        if self.current >= self.end {
             return None;
         }
-        let res = self.list.get(self.current);
-        match res {
-            Ok(elem) => {
-                self.current += 1;
-                elem
-            }
-            Err(_) => {
-                self.current = self.size;
-                None
-            }
+        let elem = self.get(self.current);
+        self.current += 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.current).max(0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a: 'b, 'b: 'c, 'c> DoubleEndedIterator for JListIter<'a, 'b, 'c> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current >= self.end {
+            return None;
         }
+        let elem = self.get(self.end - 1);
+        self.end -= 1;
+        Some(elem)
     }
 }
+
+impl<'a: 'b, 'b: 'c, 'c> ExactSizeIterator for JListIter<'a, 'b, 'c> {}
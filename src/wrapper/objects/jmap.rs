@@ -1,7 +1,8 @@
 use crate::{
     errors::*,
-    objects::{AutoLocal, JMethodID, JObject, JValue},
+    objects::{AutoLocal, JCollection, JIterable, JIterator, JMethodID, JObject, JSet, JValue},
     signature::{Primitive, ReturnType},
+    sys::jint,
     JNIEnv,
 };
 
@@ -16,6 +17,9 @@ pub struct JMap<'a: 'b, 'b> {
     get: JMethodID,
     put: JMethodID,
     remove: JMethodID,
+    contains_key: JMethodID,
+    size: JMethodID,
+    clear: JMethodID,
     env: &'b JNIEnv<'a>,
 }
 
@@ -51,12 +55,19 @@ impl<'a: 'b, 'b> JMap<'a, 'b> {
         let remove =
             env.get_method_id(&class, "remove", "(Ljava/lang/Object;)Ljava/lang/Object;")?;
 
+        let contains_key = env.get_method_id(&class, "containsKey", "(Ljava/lang/Object;)Z")?;
+        let size = env.get_method_id(&class, "size", "()I")?;
+        let clear = env.get_method_id(&class, "clear", "()V")?;
+
         Ok(JMap {
             internal: obj,
             class,
             get,
             put,
             remove,
+            contains_key,
+            size,
+            clear,
             env,
         })
     }
@@ -118,19 +129,106 @@ impl<'a: 'b, 'b> JMap<'a, 'b> {
         }
     }
 
-    /// Get key/value iterator for the map. This is done by getting the
-    /// `EntrySet` from java and iterating over it.
-    pub fn iter(&self) -> Result<JMapIter<'a, 'b, '_>> {
-        let iter_class = self
-            .env
-            .auto_local(self.env.find_class("java/util/Iterator")?);
+    /// Check whether the map contains a value for a key.
+    pub fn contains_key(&self, key: JObject<'a>) -> Result<bool> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.contains_key,
+                ReturnType::Primitive(Primitive::Boolean),
+                &[JValue::from(key).to_jni()],
+            )?
+            .z()
+    }
+
+    /// Get the number of entries in the map.
+    pub fn size(&self) -> Result<jint> {
+        self.env
+            .call_method_unchecked(
+                self.internal,
+                self.size,
+                ReturnType::Primitive(Primitive::Int),
+                &[],
+            )?
+            .i()
+    }
+
+    /// Check whether the map is empty.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.size()? == 0)
+    }
 
-        let has_next = self.env.get_method_id(&iter_class, "hasNext", "()Z")?;
+    /// Remove all the entries from the map.
+    pub fn clear(&self) -> Result<()> {
+        self.env.call_method_unchecked(
+            self.internal,
+            self.clear,
+            ReturnType::Primitive(Primitive::Void),
+            &[],
+        )?;
+        Ok(())
+    }
+
+    /// Copy all the entries of another map into this one.
+    pub fn put_all(&self, other: JObject<'a>) -> Result<()> {
+        self.env.call_method_unchecked(
+            self.internal,
+            (&self.class, "putAll", "(Ljava/util/Map;)V"),
+            ReturnType::Primitive(Primitive::Void),
+            &[JValue::from(other).to_jni()],
+        )?;
+        Ok(())
+    }
+
+    /// Get a view of the keys of the map. Removing keys from the set removes
+    /// the matching entries from the map.
+    pub fn key_set(&self) -> Result<JSet<'a, 'b>> {
+        let set = self
+            .env
+            .call_method_unchecked(
+                self.internal,
+                (&self.class, "keySet", "()Ljava/util/Set;"),
+                ReturnType::Object,
+                &[],
+            )?
+            .l()?;
+        JSet::from_env(self.env, set)
+    }
 
-        let next = self
+    /// Get a view of the values of the map. Removing values from the
+    /// collection removes the matching entries from the map.
+    pub fn values(&self) -> Result<JCollection<'a, 'b>> {
+        let values = self
             .env
-            .get_method_id(&iter_class, "next", "()Ljava/lang/Object;")?;
+            .call_method_unchecked(
+                self.internal,
+                (&self.class, "values", "()Ljava/util/Collection;"),
+                ReturnType::Object,
+                &[],
+            )?
+            .l()?;
+        JCollection::from_env(self.env, values)
+    }
+
+    /// Get an iterator over the keys of the map, see `JIterator`.
+    pub fn iter_keys(&self) -> Result<JIterator<'a, 'b>> {
+        let keys = self.key_set()?;
+        let iter = keys.iter();
+        self.env.delete_local_ref(**keys)?;
+        iter
+    }
+
+    /// Get an iterator over the values of the map, see `JIterator`.
+    pub fn iter_values(&self) -> Result<JIterator<'a, 'b>> {
+        let values = self.values()?;
+        let iter = values.iter();
+        self.env.delete_local_ref(*values)?;
+        iter
+    }
 
+    /// Get key/value iterator for the map. This is done by getting the
+    /// `EntrySet` from java and iterating over it.
+    pub fn iter(&self) -> Result<JMapIter<'a, 'b, '_>> {
         let entry_class = self
             .env
             .auto_local(self.env.find_class("java/util/Map$Entry")?);
@@ -146,35 +244,21 @@ impl<'a: 'b, 'b> JMap<'a, 'b> {
         // Get the iterator over Map entries.
         // Use the local frame till #109 is resolved, so that implicitly looked-up
         // classes are freed promptly.
-        let iter = self.env.with_local_frame(16, || {
-            let entry_set = self
-                .env
+        let entry_set = self.env.with_local_frame(16, || {
+            self.env
                 .call_method_unchecked(
                     self.internal,
                     (&self.class, "entrySet", "()Ljava/util/Set;"),
                     ReturnType::Object,
                     &[],
                 )?
-                .l()?;
-
-            let iter = self
-                .env
-                .call_method_unchecked(
-                    entry_set,
-                    ("java/util/Set", "iterator", "()Ljava/util/Iterator;"),
-                    ReturnType::Object,
-                    &[],
-                )?
-                .l()?;
-
-            Ok(iter)
+                .l()
         })?;
-        let iter = self.env.auto_local(iter);
+        let entry_set = self.env.auto_local(entry_set);
+        let iter = JIterable::from_env(self.env, entry_set.as_obj())?.iter()?;
 
         Ok(JMapIter {
             map: self,
-            has_next,
-            next,
             get_key,
             get_value,
             iter,
@@ -183,51 +267,47 @@ impl<'a: 'b, 'b> JMap<'a, 'b> {
 }
 
 /// An iterator over the keys and values in a map.
-///
-/// TODO: make the iterator implementation for java iterators its own thing
-/// and generic enough to use elsewhere.
-pub struct JMapIter<'a, 'b, 'c> {
+pub struct JMapIter<'a: 'b, 'b: 'c, 'c> {
     map: &'c JMap<'a, 'b>,
-    has_next: JMethodID,
-    next: JMethodID,
     get_key: JMethodID,
     get_value: JMethodID,
-    iter: AutoLocal<'a, 'b>,
+    iter: JIterator<'a, 'b>,
 }
 
 impl<'a: 'b, 'b: 'c, 'c> JMapIter<'a, 'b, 'c> {
-    fn get_next(&self) -> Result<Option<(JObject<'a>, JObject<'a>)>> {
-        let iter = self.iter.as_obj();
-        let has_next = self
+    /// Get the key and value of the next entry. Returns `None` at the end of
+    /// the iteration.
+    ///
+    /// Unlike with `Iterator#next`, errors are returned rather than ending the
+    /// iteration.
+    pub fn next_entry(&self) -> Result<Option<(JObject<'a>, JObject<'a>)>> {
+        // The local reference to the entry is deleted once the key and value
+        // are looked up.
+        let next = match self.iter.next_elem()? {
+            Some(next) => self.map.env.auto_local(next),
+            None => return Ok(None),
+        };
+
+        let key = self
             .map
             .env
             .call_method_unchecked(
-                iter,
-                self.has_next,
-                ReturnType::Primitive(Primitive::Boolean),
+                next.as_obj().into_inner(),
+                self.get_key,
+                ReturnType::Object,
                 &[],
             )?
-            .z()?;
-
-        if !has_next {
-            return Ok(None);
-        }
-        let next = self
-            .map
-            .env
-            .call_method_unchecked(iter, self.next, ReturnType::Object, &[])?
-            .l()?;
-
-        let key = self
-            .map
-            .env
-            .call_method_unchecked(next, self.get_key, ReturnType::Object, &[])?
             .l()?;
 
         let value = self
             .map
             .env
-            .call_method_unchecked(next, self.get_value, ReturnType::Object, &[])?
+            .call_method_unchecked(
+                next.as_obj().into_inner(),
+                self.get_value,
+                ReturnType::Object,
+                &[],
+            )?
             .l()?;
 
         Ok(Some((key, value)))
@@ -238,7 +318,7 @@ impl<'a: 'b, 'b: 'c, 'c> Iterator for JMapIter<'a, 'b, 'c> {
     type Item = (JObject<'a>, JObject<'a>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.get_next() {
+        match self.next_entry() {
             Ok(Some(n)) => Some(n),
             _ => None,
         }
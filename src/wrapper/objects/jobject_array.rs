@@ -0,0 +1,100 @@
+use crate::{
+    errors::*,
+    objects::{AutoLocal, JObject},
+    sys::{jobject, jobjectArray, jsize},
+    JNIEnv,
+};
+
+/// Lifetime'd representation of a `jobjectArray`. Just a `JObject` wrapped in a
+/// new class.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JObjectArray<'a>(JObject<'a>);
+
+impl<'a> From<jobjectArray> for JObjectArray<'a> {
+    fn from(other: jobjectArray) -> Self {
+        JObjectArray(From::from(other as jobject))
+    }
+}
+
+impl<'a> ::std::ops::Deref for JObjectArray<'a> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> From<JObjectArray<'a>> for JObject<'a> {
+    fn from(other: JObjectArray) -> JObject {
+        other.0
+    }
+}
+
+impl<'a> From<JObject<'a>> for JObjectArray<'a> {
+    fn from(other: JObject) -> JObjectArray {
+        (other.into_inner() as jobjectArray).into()
+    }
+}
+
+/// A double-ended iterator over the elements of an object array, with an exact
+/// size, see `JNIEnv#get_object_array_iter`.
+///
+/// The local reference to each element is deleted when it is dropped, so that
+/// iterating over a long array doesn't overflow the local reference table.
+/// The length of an array can't change, so getting an element only fails on
+/// JNI errors, which are yielded as they are.
+pub struct JObjectArrayIter<'a: 'b, 'b> {
+    array: JObjectArray<'a>,
+    current: jsize,
+    end: jsize,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JObjectArrayIter<'a, 'b> {
+    pub(crate) fn from_env(env: &'b JNIEnv<'a>, array: JObjectArray<'a>) -> Result<Self> {
+        let end = env.get_array_length(array)?;
+        Ok(JObjectArrayIter {
+            array,
+            current: 0,
+            end,
+            env,
+        })
+    }
+
+    fn get(&self, idx: jsize) -> Result<AutoLocal<'a, 'b>> {
+        let elem = self.env.get_object_array_element(self.array, idx)?;
+        Ok(self.env.auto_local(elem))
+    }
+}
+
+impl<'a: 'b, 'b> Iterator for JObjectArrayIter<'a, 'b> {
+    type Item = Result<AutoLocal<'a, 'b>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current >= self.end {
+            return None;
+        }
+        let elem = self.get(self.current);
+        self.current += 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.current).max(0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a: 'b, 'b> DoubleEndedIterator for JObjectArrayIter<'a, 'b> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current >= self.end {
+            return None;
+        }
+        let elem = self.get(self.end - 1);
+        self.end -= 1;
+        Some(elem)
+    }
+}
+
+impl<'a: 'b, 'b> ExactSizeIterator for JObjectArrayIter<'a, 'b> {}
@@ -0,0 +1,28 @@
+use crate::sys::jobjectRefType;
+
+/// The kind of a JNI reference, as reported by `GetObjectRefType`.
+///
+/// See [`JNIEnv::get_object_ref_type`](crate::JNIEnv::get_object_ref_type).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JObjectRefType {
+    /// Not a valid reference, e.g. `null`, a dangling pointer or a reference
+    /// that has already been deleted.
+    Invalid,
+    /// A local reference, valid in the current frame of the current thread.
+    Local,
+    /// A global reference.
+    Global,
+    /// A weak global reference.
+    WeakGlobal,
+}
+
+impl From<jobjectRefType> for JObjectRefType {
+    fn from(other: jobjectRefType) -> Self {
+        match other {
+            jobjectRefType::JNIInvalidRefType => JObjectRefType::Invalid,
+            jobjectRefType::JNILocalRefType => JObjectRefType::Local,
+            jobjectRefType::JNIGlobalRefType => JObjectRefType::Global,
+            jobjectRefType::JNIWeakGlobalRefType => JObjectRefType::WeakGlobal,
+        }
+    }
+}
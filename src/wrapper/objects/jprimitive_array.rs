@@ -0,0 +1,149 @@
+use std::{borrow::Cow, fmt, marker::PhantomData, slice};
+
+use crate::{
+    objects::{JObject, TypeArray},
+    sys::{jarray, jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort},
+};
+
+/// Lifetime'd representation of a Java array of primitives, e.g. a
+/// `jintArray` for `JPrimitiveArray<'a, jint>` (aliased as `JIntArray<'a>`).
+/// Just a `JObject` wrapped in a new class, which carries the element type.
+#[repr(transparent)]
+pub struct JPrimitiveArray<'a, T: TypeArray> {
+    internal: JObject<'a>,
+    element: PhantomData<T>,
+}
+
+// Implemented manually, as deriving would require `T` to implement the traits.
+impl<'a, T: TypeArray> Clone for JPrimitiveArray<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: TypeArray> Copy for JPrimitiveArray<'a, T> {}
+
+impl<'a, T: TypeArray> fmt::Debug for JPrimitiveArray<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("JPrimitiveArray")
+            .field(&self.internal)
+            .finish()
+    }
+}
+
+/// Lifetime'd representation of a `jbooleanArray`.
+pub type JBooleanArray<'a> = JPrimitiveArray<'a, jboolean>;
+/// Lifetime'd representation of a `jbyteArray`.
+pub type JByteArray<'a> = JPrimitiveArray<'a, jbyte>;
+/// Lifetime'd representation of a `jcharArray`.
+pub type JCharArray<'a> = JPrimitiveArray<'a, jchar>;
+/// Lifetime'd representation of a `jshortArray`.
+pub type JShortArray<'a> = JPrimitiveArray<'a, jshort>;
+/// Lifetime'd representation of a `jintArray`.
+pub type JIntArray<'a> = JPrimitiveArray<'a, jint>;
+/// Lifetime'd representation of a `jlongArray`.
+pub type JLongArray<'a> = JPrimitiveArray<'a, jlong>;
+/// Lifetime'd representation of a `jfloatArray`.
+pub type JFloatArray<'a> = JPrimitiveArray<'a, jfloat>;
+/// Lifetime'd representation of a `jdoubleArray`.
+pub type JDoubleArray<'a> = JPrimitiveArray<'a, jdouble>;
+
+impl<'a, T: TypeArray> From<jarray> for JPrimitiveArray<'a, T> {
+    fn from(other: jarray) -> Self {
+        JPrimitiveArray {
+            internal: From::from(other as jobject),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: TypeArray> ::std::ops::Deref for JPrimitiveArray<'a, T> {
+    type Target = JObject<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a, T: TypeArray> From<JPrimitiveArray<'a, T>> for JObject<'a> {
+    fn from(other: JPrimitiveArray<'a, T>) -> JObject<'a> {
+        other.internal
+    }
+}
+
+impl<'a, T: TypeArray> From<JObject<'a>> for JPrimitiveArray<'a, T> {
+    fn from(other: JObject<'a>) -> JPrimitiveArray<'a, T> {
+        (other.into_inner() as jarray).into()
+    }
+}
+
+impl<'a, T: TypeArray> std::default::Default for JPrimitiveArray<'a, T> {
+    fn default() -> Self {
+        JObject::null().into()
+    }
+}
+
+/// Rust types that can be converted in bulk to and from the elements of a Java
+/// primitive array, see `JNIEnv#array_from_slice` and `JNIEnv#convert_array`.
+///
+/// Implemented for the Java primitive types themselves, except `jboolean`, for
+/// `bool` (as a `boolean[]`) and for `u8` (as a `byte[]`, like
+/// `JNIEnv#byte_array_from_slice`). As `jboolean` is an alias of `u8`, raw
+/// `jboolean` elements can be copied with `JNIEnv#set_array_region` and
+/// `JNIEnv#get_array_region` instead.
+pub trait ArrayElement: Sized {
+    /// The element type of the Java array.
+    type Elem: TypeArray + Copy + Default;
+
+    /// Converts a slice to the elements of the Java array, borrowing it if the
+    /// types have the same representation.
+    fn to_elems(buf: &[Self]) -> Cow<'_, [Self::Elem]>;
+
+    /// Converts the elements of the Java array.
+    fn from_elems(elems: Vec<Self::Elem>) -> Vec<Self>;
+}
+
+macro_rules! array_element {
+    ( $( $jni_type:ty ),* ) => {
+        $(
+            impl ArrayElement for $jni_type {
+                type Elem = $jni_type;
+
+                fn to_elems(buf: &[Self]) -> Cow<'_, [Self::Elem]> {
+                    Cow::Borrowed(buf)
+                }
+
+                fn from_elems(elems: Vec<Self::Elem>) -> Vec<Self> {
+                    elems
+                }
+            }
+        )*
+    };
+}
+
+array_element!(jbyte, jchar, jshort, jint, jlong, jfloat, jdouble);
+
+impl ArrayElement for bool {
+    type Elem = jboolean;
+
+    fn to_elems(buf: &[Self]) -> Cow<'_, [Self::Elem]> {
+        Cow::Owned(buf.iter().map(|&b| b as jboolean).collect())
+    }
+
+    fn from_elems(elems: Vec<Self::Elem>) -> Vec<Self> {
+        elems.into_iter().map(|b| b != 0).collect()
+    }
+}
+
+impl ArrayElement for u8 {
+    type Elem = jbyte;
+
+    fn to_elems(buf: &[Self]) -> Cow<'_, [Self::Elem]> {
+        // `u8` and `i8` have the same size and alignment.
+        Cow::Borrowed(unsafe { slice::from_raw_parts(buf.as_ptr() as *const jbyte, buf.len()) })
+    }
+
+    fn from_elems(elems: Vec<Self::Elem>) -> Vec<Self> {
+        elems.into_iter().map(|b: jbyte| b as u8).collect()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    errors::*,
+    objects::{JCollection, JObject},
+    JNIEnv,
+};
+
+/// Wrapper for JObjects that implement `java/util/Set`. Provides the methods
+/// of `JCollection`, which it derefs to.
+pub struct JSet<'a: 'b, 'b> {
+    internal: JCollection<'a, 'b>,
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JSet<'a, 'b> {
+    type Target = JCollection<'a, 'b>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.internal
+    }
+}
+
+impl<'a: 'b, 'b> From<JSet<'a, 'b>> for JObject<'a> {
+    fn from(other: JSet<'a, 'b>) -> JObject<'a> {
+        other.internal.into()
+    }
+}
+
+impl<'a: 'b, 'b> From<JSet<'a, 'b>> for JCollection<'a, 'b> {
+    fn from(other: JSet<'a, 'b>) -> JCollection<'a, 'b> {
+        other.internal
+    }
+}
+
+impl<'a: 'b, 'b> JSet<'a, 'b> {
+    /// Create a set from the environment and an object. This looks up the
+    /// necessary class and method ids to call all of the methods on it so that
+    /// exra work doesn't need to be done on every method call.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Result<JSet<'a, 'b>> {
+        Ok(JSet {
+            internal: JCollection::with_class(env, obj, "java/util/Set")?,
+        })
+    }
+
+    /// Create a new, empty `java.util.HashSet`.
+    pub fn new_hash_set(env: &'b JNIEnv<'a>) -> Result<JSet<'a, 'b>> {
+        let obj = env.new_object("java/util/HashSet", "()V", &[])?;
+        JSet::from_env(env, obj)
+    }
+}
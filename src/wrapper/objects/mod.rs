@@ -32,13 +32,39 @@ pub use self::jmap::*;
 mod jlist;
 pub use self::jlist::*;
 
+mod jiterator;
+pub use self::jiterator::*;
+
+mod jcollection;
+pub use self::jcollection::*;
+
+mod jset;
+pub use self::jset::*;
+
 mod jbytebuffer;
 pub use self::jbytebuffer::*;
 
+mod jprimitive_array;
+pub use self::jprimitive_array::*;
+
+mod jobject_array;
+pub use self::jobject_array::*;
+
+// For boxing primitives into their `java.lang` wrapper classes
+mod boxed;
+pub use self::boxed::*;
+
+mod jobject_ref_type;
+pub use self::jobject_ref_type::*;
+
 // For storing a reference to a java object
 mod global_ref;
 pub use self::global_ref::*;
 
+// For storing a weak reference to a java object
+mod weak_ref;
+pub use self::weak_ref::*;
+
 // For automatic local ref deletion
 mod auto_local;
 pub use self::auto_local::*;
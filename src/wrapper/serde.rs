@@ -0,0 +1,828 @@
+use std::{char, convert::TryFrom, fmt::Display};
+
+use ::serde::{
+    de::{
+        self, value::SeqDeserializer, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor,
+    },
+    ser::{self, Serialize},
+};
+
+use crate::{
+    errors::*,
+    java_class::LazyClass,
+    objects::{
+        ArrayElement, AutoLocal, Boxable, GlobalRef, JCollection, JList, JMap, JObject,
+        JObjectArray, JPrimitiveArray,
+    },
+    sys::{jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+    JNIEnv,
+};
+
+// Returns the class with the given name, which is looked up once, as looking
+// it up by name for every object would leave a local reference behind each
+// time.
+macro_rules! class {
+    ($env:expr, $name:literal) => {{
+        static CLASS: LazyClass = LazyClass::new();
+        CLASS.get($env, $name)?
+    }};
+}
+
+/// Serializes a value into a graph of Java objects, see `Serializer`.
+pub fn to_java<'a, T: Serialize + ?Sized>(env: &JNIEnv<'a>, value: &T) -> Result<JObject<'a>> {
+    value.serialize(Serializer::new(env))
+}
+
+/// Deserializes a value from a graph of Java objects, see `Deserializer`.
+///
+/// The local reference to `obj` is kept, while the local references to the
+/// objects it contains are deleted once they have been deserialized.
+pub fn from_java<'a, T: DeserializeOwned>(env: &JNIEnv<'a>, obj: JObject<'a>) -> Result<T> {
+    T::deserialize(Deserializer::new(env, obj))
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Conversion(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Conversion(msg.to_string())
+    }
+}
+
+/// A serde `Serializer` that creates Java objects, returning a new local
+/// reference to the serialized value:
+///
+/// - `bool`, `i8`, `i16`, `i32`, `i64`, `f32` and `f64` are boxed into their
+///   `java.lang` wrapper class, and `u8`, `u16` and `u32` into the next wider
+///   one (e.g. a `u16` into an `Integer`). A `u64` is boxed into a `Long`,
+///   and rejected if it is out of range.
+/// - `char` is boxed into a `Character`, or a `String` if it isn't in the
+///   Basic Multilingual Plane.
+/// - strings and unit variants are `java.lang.String`s, and bytes (e.g. with
+///   `serde_bytes`) `byte[]`s.
+/// - `None` and `()` are `null`, and `Some` and newtype structs are
+///   serialized as their value.
+/// - sequences and tuples are `java.util.ArrayList`s.
+/// - maps and structs are `java.util.HashMap`s, with the field names as
+///   keys for structs.
+/// - other enum variants are a `java.util.HashMap` with the variant name as
+///   the only key, like the externally tagged representation of JSON.
+///
+/// The local references to the objects of collections and maps are deleted
+/// once they have been added.
+#[derive(Clone, Copy)]
+pub struct Serializer<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> Serializer<'a, 'b> {
+    /// Creates a serializer.
+    pub fn new(env: &'b JNIEnv<'a>) -> Self {
+        Serializer { env }
+    }
+
+    fn serialize_local<T: Serialize + ?Sized>(&self, value: &T) -> Result<AutoLocal<'a, 'b>> {
+        Ok(self.env.auto_local(value.serialize(*self)?))
+    }
+
+    // Wraps the value of an enum variant into a map with the variant name as
+    // key.
+    fn variant_map(&self, variant: &'static str, value: JObject<'a>) -> Result<JObject<'a>> {
+        let value = self.env.auto_local(value);
+        let map = JMap::from_env(
+            self.env,
+            self.env
+                .new_object(class!(self.env, "java/util/HashMap"), "()V", &[])?,
+        )?;
+        let key = self.env.auto_local(self.env.new_string(variant)?);
+        map.put(key.as_obj(), value.as_obj())?;
+        Ok(map.into())
+    }
+}
+
+impl<'a: 'b, 'b> ser::Serializer for Serializer<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+    type SerializeSeq = SerializeList<'a, 'b>;
+    type SerializeTuple = SerializeList<'a, 'b>;
+    type SerializeTupleStruct = SerializeList<'a, 'b>;
+    type SerializeTupleVariant = SerializeList<'a, 'b>;
+    type SerializeMap = SerializeMap<'a, 'b>;
+    type SerializeStruct = SerializeMap<'a, 'b>;
+    type SerializeStructVariant = SerializeMap<'a, 'b>;
+
+    fn serialize_bool(self, v: bool) -> Result<JObject<'a>> {
+        self.env.box_value(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<JObject<'a>> {
+        self.env.box_value(v as jbyte)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<JObject<'a>> {
+        self.env.box_value(v as jshort)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<JObject<'a>> {
+        self.env.box_value(v as jint)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<JObject<'a>> {
+        self.env.box_value(v as jlong)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<JObject<'a>> {
+        self.env.box_value(jshort::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<JObject<'a>> {
+        self.env.box_value(jint::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<JObject<'a>> {
+        self.env.box_value(jlong::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<JObject<'a>> {
+        let v = jlong::try_from(v)
+            .map_err(|_| Error::Conversion(format!("u64 {} is out of range for a Java long", v)))?;
+        self.env.box_value(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<JObject<'a>> {
+        self.env.box_value(v as jfloat)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<JObject<'a>> {
+        self.env.box_value(v as jdouble)
+    }
+
+    fn serialize_char(self, v: char) -> Result<JObject<'a>> {
+        match jchar::try_from(u32::from(v)) {
+            Ok(c) => self.env.box_value(c),
+            Err(_) => self.serialize_str(v.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    fn serialize_str(self, v: &str) -> Result<JObject<'a>> {
+        Ok(self.env.new_string(v)?.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JObject<'a>> {
+        Ok(self.env.byte_array_from_slice(v)?.into())
+    }
+
+    fn serialize_none(self) -> Result<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<JObject<'a>> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JObject<'a>> {
+        Ok(JObject::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JObject<'a>> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<JObject<'a>> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<JObject<'a>> {
+        let value = value.serialize(self)?;
+        self.variant_map(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList<'a, 'b>> {
+        SerializeList::new(self, len.unwrap_or(0), None)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList<'a, 'b>> {
+        SerializeList::new(self, len, None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeList<'a, 'b>> {
+        SerializeList::new(self, len, None)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeList<'a, 'b>> {
+        SerializeList::new(self, len, Some(variant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self, None)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self, None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeMap<'a, 'b>> {
+        SerializeMap::new(self, Some(variant))
+    }
+}
+
+/// Serializes sequences, tuples and tuple variants into a
+/// `java.util.ArrayList`, see `Serializer`.
+pub struct SerializeList<'a: 'b, 'b> {
+    serializer: Serializer<'a, 'b>,
+    list: JList<'a, 'b>,
+    variant: Option<&'static str>,
+}
+
+impl<'a: 'b, 'b> SerializeList<'a, 'b> {
+    fn new(
+        serializer: Serializer<'a, 'b>,
+        capacity: usize,
+        variant: Option<&'static str>,
+    ) -> Result<Self> {
+        let env = serializer.env;
+        let capacity = jint::try_from(capacity).unwrap_or(0);
+        let list = env.new_object(
+            class!(env, "java/util/ArrayList"),
+            "(I)V",
+            &[capacity.into()],
+        )?;
+        Ok(SerializeList {
+            serializer,
+            list: JList::from_env(env, list)?,
+            variant,
+        })
+    }
+
+    fn add<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let value = self.serializer.serialize_local(value)?;
+        self.list.add(value.as_obj())
+    }
+
+    fn finish(self) -> Result<JObject<'a>> {
+        let list = self.list.into();
+        match self.variant {
+            Some(variant) => self.serializer.variant_map(variant, list),
+            None => Ok(list),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeSeq for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTuple for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTupleStruct for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeTupleVariant for SerializeList<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+/// Serializes maps, structs and struct variants into a `java.util.HashMap`,
+/// see `Serializer`.
+pub struct SerializeMap<'a: 'b, 'b> {
+    serializer: Serializer<'a, 'b>,
+    map: JMap<'a, 'b>,
+    key: Option<AutoLocal<'a, 'b>>,
+    variant: Option<&'static str>,
+}
+
+impl<'a: 'b, 'b> SerializeMap<'a, 'b> {
+    fn new(serializer: Serializer<'a, 'b>, variant: Option<&'static str>) -> Result<Self> {
+        let env = serializer.env;
+        Ok(SerializeMap {
+            serializer,
+            map: JMap::from_env(
+                env,
+                env.new_object(class!(env, "java/util/HashMap"), "()V", &[])?,
+            )?,
+            key: None,
+            variant,
+        })
+    }
+
+    fn put(&self, key: &AutoLocal<'a, 'b>, value: &AutoLocal<'a, 'b>) -> Result<()> {
+        if let Some(previous) = self.map.put(key.as_obj(), value.as_obj())? {
+            self.serializer.env.delete_local_ref(previous)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<JObject<'a>> {
+        let map = self.map.into();
+        match self.variant {
+            Some(variant) => self.serializer.variant_map(variant, map),
+            None => Ok(map),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeMap for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(self.serializer.serialize_local(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Conversion("map value serialized before its key".to_string()))?;
+        let value = self.serializer.serialize_local(value)?;
+        self.put(&key, &value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeStruct for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let key = self.serializer.serialize_local(key)?;
+        let value = self.serializer.serialize_local(value)?;
+        self.put(&key, &value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+impl<'a: 'b, 'b> ser::SerializeStructVariant for SerializeMap<'a, 'b> {
+    type Ok = JObject<'a>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<JObject<'a>> {
+        self.finish()
+    }
+}
+
+/// A serde `Deserializer` that reads Java objects:
+///
+/// - `null` is `None`, or `()`.
+/// - `java.lang.String`s are strings, and boxed primitives the primitive
+///   value. Integers and floats can be deserialized into any Rust type they
+///   fit in, e.g. an `Integer` into a `u8` if it is in range.
+/// - `byte[]` is bytes (e.g. with `serde_bytes`), or a sequence of `i8`, and
+///   the other primitive arrays are sequences of their values.
+/// - object arrays and `java.util.Collection`s are sequences.
+/// - `java.util.Map`s are maps, or structs.
+/// - enums are read from a `String` for unit variants, or a `Map` with the
+///   variant name as the only key, as created by `Serializer`.
+///
+/// Objects of other classes are rejected with `Error::Conversion`. The local
+/// references to the objects contained in arrays, collections and maps are
+/// deleted once they have been deserialized.
+pub struct Deserializer<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    obj: JObject<'a>,
+}
+
+impl<'a: 'b, 'b> Deserializer<'a, 'b> {
+    /// Creates a deserializer for an object, which may be `null`.
+    pub fn new(env: &'b JNIEnv<'a>, obj: JObject<'a>) -> Self {
+        Deserializer { env, obj }
+    }
+
+    fn is_instance_of(&self, class: &GlobalRef) -> Result<bool> {
+        self.env.is_instance_of(self.obj, class)
+    }
+
+    fn is_boxed<T: Boxable>(&self) -> Result<bool> {
+        self.env.is_instance_of(self.obj, T::class(self.env)?)
+    }
+
+    fn deserialize_seq_of<'de, T, V>(&self, visitor: V) -> Result<V::Value>
+    where
+        T: ArrayElement + IntoDeserializer<'de, Error>,
+        V: Visitor<'de>,
+    {
+        let values = self
+            .env
+            .convert_array::<T>(JPrimitiveArray::from(self.obj))?;
+        visit_values(visitor, values)
+    }
+}
+
+fn visit_values<'de, T, V>(visitor: V, values: Vec<T>) -> Result<V::Value>
+where
+    T: IntoDeserializer<'de, Error>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(values.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+// Iterations over Java collections end on the first error, leaving the
+// exception pending, which is checked for once they end.
+fn check_exhausted(env: &JNIEnv) -> Result<()> {
+    if env.exception_check()? {
+        return Err(env.pending_exception_error());
+    }
+    Ok(())
+}
+
+impl<'de, 'a: 'b, 'b> de::Deserializer<'de> for Deserializer<'a, 'b> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let env = self.env;
+        let obj = self.obj;
+
+        if obj.is_null() {
+            return visitor.visit_unit();
+        }
+        if self.is_instance_of(class!(self.env, "java/lang/String"))? {
+            return visitor.visit_string(env.get_string(obj.into())?.into());
+        }
+
+        if self.is_boxed::<jint>()? {
+            return visitor.visit_i32(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jlong>()? {
+            return visitor.visit_i64(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jdouble>()? {
+            return visitor.visit_f64(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<bool>()? {
+            return visitor.visit_bool(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jfloat>()? {
+            return visitor.visit_f32(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jshort>()? {
+            return visitor.visit_i16(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jbyte>()? {
+            return visitor.visit_i8(env.unbox_value(obj)?);
+        }
+        if self.is_boxed::<jchar>()? {
+            let c: jchar = env.unbox_value(obj)?;
+            return match char::from_u32(u32::from(c)) {
+                Some(c) => visitor.visit_char(c),
+                None => Err(Error::Conversion(format!(
+                    "invalid char: unpaired surrogate {:#x}",
+                    c
+                ))),
+            };
+        }
+
+        if self.is_instance_of(class!(self.env, "java/util/Map"))? {
+            let map = JMap::from_env(env, obj)?;
+            let size = map.size()?;
+            let mut access = MapAccess {
+                env,
+                iter: map.iter()?,
+                value: None,
+                remaining: size.max(0) as usize,
+            };
+            let value = visitor.visit_map(&mut access)?;
+            check_exhausted(env)?;
+            return Ok(value);
+        }
+        if self.is_instance_of(class!(self.env, "java/util/Collection"))? {
+            let collection = JCollection::from_env(env, obj)?;
+            let size = collection.size()?;
+            let value = visitor.visit_seq(SeqAccess {
+                env,
+                iter: collection.iter()?.map(Ok),
+                remaining: size.max(0) as usize,
+            })?;
+            check_exhausted(env)?;
+            return Ok(value);
+        }
+
+        if self.is_instance_of(class!(self.env, "[Ljava/lang/Object;"))? {
+            let array = JObjectArray::from(obj);
+            let size = env.get_array_length(array)?;
+            let value = visitor.visit_seq(SeqAccess {
+                env,
+                iter: env.get_object_array_iter(array)?,
+                remaining: size.max(0) as usize,
+            })?;
+            check_exhausted(env)?;
+            return Ok(value);
+        }
+        if self.is_instance_of(class!(self.env, "[B"))? {
+            return visitor.visit_byte_buf(env.convert_byte_array(obj.into())?);
+        }
+        if self.is_instance_of(class!(self.env, "[C"))? {
+            let chars: Vec<jchar> = env.convert_array(obj.into())?;
+            let chars = char::decode_utf16(chars)
+                .collect::<std::result::Result<Vec<char>, _>>()
+                .map_err(|e| Error::Conversion(format!("invalid char: {}", e)))?;
+            return visit_values(visitor, chars);
+        }
+        if self.is_instance_of(class!(self.env, "[Z"))? {
+            return self.deserialize_seq_of::<bool, V>(visitor);
+        }
+        if self.is_instance_of(class!(self.env, "[S"))? {
+            return self.deserialize_seq_of::<jshort, V>(visitor);
+        }
+        if self.is_instance_of(class!(self.env, "[I"))? {
+            return self.deserialize_seq_of::<jint, V>(visitor);
+        }
+        if self.is_instance_of(class!(self.env, "[J"))? {
+            return self.deserialize_seq_of::<jlong, V>(visitor);
+        }
+        if self.is_instance_of(class!(self.env, "[F"))? {
+            return self.deserialize_seq_of::<jfloat, V>(visitor);
+        }
+        if self.is_instance_of(class!(self.env, "[D"))? {
+            return self.deserialize_seq_of::<jdouble, V>(visitor);
+        }
+
+        Err(Error::Conversion(
+            "unsupported Java object, expected null, a String, a boxed primitive, \
+             an array, a Collection or a Map"
+                .to_string(),
+        ))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.obj.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if !self.obj.is_null() && self.is_instance_of(class!(self.env, "[B"))? {
+            return self.deserialize_seq_of::<jbyte, V>(visitor);
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let env = self.env;
+        non_null!(self.obj, "Deserializer::deserialize_enum obj argument");
+
+        if self.is_instance_of(class!(self.env, "java/lang/String"))? {
+            let variant: String = env.get_string(self.obj.into())?.into();
+            return visitor.visit_enum(variant.into_deserializer());
+        }
+        if self.is_instance_of(class!(self.env, "java/util/Map"))? {
+            let map = JMap::from_env(env, self.obj)?;
+            if map.size()? == 1 {
+                let entry = map.iter()?.next();
+                check_exhausted(env)?;
+                if let Some((variant, value)) = entry {
+                    return visitor.visit_enum(EnumAccess {
+                        env,
+                        variant: env.auto_local(variant),
+                        value: env.auto_local(value),
+                    });
+                }
+            }
+        }
+        Err(Error::Conversion(
+            "expected an enum variant, as a String or a Map with a single entry".to_string(),
+        ))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct identifier
+    }
+}
+
+struct SeqAccess<'a: 'b, 'b, I> {
+    env: &'b JNIEnv<'a>,
+    iter: I,
+    remaining: usize,
+}
+
+impl<'de, 'a: 'b + 'c, 'b, 'c, I> de::SeqAccess<'de> for SeqAccess<'a, 'b, I>
+where
+    I: Iterator<Item = Result<AutoLocal<'a, 'c>>>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.iter.next() {
+            Some(elem) => {
+                let elem = elem?;
+                self.remaining = self.remaining.saturating_sub(1);
+                seed.deserialize(Deserializer::new(self.env, elem.as_obj()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct MapAccess<'a: 'b, 'b, I> {
+    env: &'b JNIEnv<'a>,
+    iter: I,
+    value: Option<AutoLocal<'a, 'b>>,
+    remaining: usize,
+}
+
+impl<'de, 'a: 'b, 'b, I> de::MapAccess<'de> for MapAccess<'a, 'b, I>
+where
+    I: Iterator<Item = (JObject<'a>, JObject<'a>)>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.remaining = self.remaining.saturating_sub(1);
+                let key = self.env.auto_local(key);
+                self.value = Some(self.env.auto_local(value));
+                seed.deserialize(Deserializer::new(self.env, key.as_obj()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self.value.take().ok_or_else(|| {
+            Error::Conversion("map value deserialized before its key".to_string())
+        })?;
+        seed.deserialize(Deserializer::new(self.env, value.as_obj()))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+struct EnumAccess<'a: 'b, 'b> {
+    env: &'b JNIEnv<'a>,
+    variant: AutoLocal<'a, 'b>,
+    value: AutoLocal<'a, 'b>,
+}
+
+impl<'de, 'a: 'b, 'b> de::EnumAccess<'de> for EnumAccess<'a, 'b> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let variant = seed.deserialize(Deserializer::new(self.env, self.variant.as_obj()))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a: 'b, 'b> de::VariantAccess<'de> for EnumAccess<'a, 'b> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(Deserializer::new(self.env, self.value.as_obj()))
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(Deserializer::new(self.env, self.value.as_obj()))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(Deserializer::new(self.env, self.value.as_obj()), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(Deserializer::new(self.env, self.value.as_obj()), visitor)
+    }
+}
@@ -1,10 +1,14 @@
-use std::{fmt, str::FromStr};
+use std::{fmt, marker::PhantomData, str::FromStr};
 
 use combine::{
     between, many, many1, parser, satisfy, token, ParseError, Parser, StdParseResult, Stream,
 };
 
-use crate::errors::*;
+use crate::{
+    errors::*,
+    objects::{JClass, JObject, JString, JThrowable, JValue},
+    sys::{jbyte, jchar, jdouble, jfloat, jint, jlong, jshort, jvalue},
+};
 
 /// A primitive java type. These are the things that can be represented without
 /// an object.
@@ -135,6 +139,210 @@ impl fmt::Display for TypeSignature {
     }
 }
 
+/// A method signature that was checked at compile time, with the Rust types
+/// of its arguments (`Args`, a tuple) and return value (`Ret`).
+///
+/// Use the `jni_sig!` macro (with the `macros` feature) to create one from a
+/// JNI signature string:
+///
+/// ```rust,ignore
+/// const INDEX_OF: MethodSignature<(JString<'static>, jint), jint> =
+///     jni_sig!("(Ljava/lang/String;I)I");
+///
+/// let index = env.call_method_typed(s, "indexOf", INDEX_OF, (needle, 0))?;
+/// ```
+///
+/// Primitive types map to their `jni::sys` type (with `bool` for `Z`) and
+/// `V` maps to `()`. `java.lang.String`, `java.lang.Class` and
+/// `java.lang.Throwable` map to `JString`, `JClass` and `JThrowable`, and all
+/// other object and array types map to `JObject`. The lifetime of object
+/// types doesn't matter here (`jni_sig!` uses `'static`); the values returned
+/// by calls get the lifetime of the `JNIEnv`.
+pub struct MethodSignature<Args, Ret> {
+    sig: &'static str,
+    _marker: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<Args, Ret> MethodSignature<Args, Ret> {
+    /// Creates a method signature without checking that `sig` is valid or that
+    /// it matches `Args` and `Ret`. Prefer the `jni_sig!` macro, which does.
+    ///
+    /// # Safety
+    ///
+    /// `sig` must be a valid method signature whose argument and return types
+    /// match `Args` and `Ret`. Calls made with a mismatched signature use the
+    /// wrong JNI call for the return type, or pass the wrong types of
+    /// arguments, which is undefined behavior.
+    pub const unsafe fn new_unchecked(sig: &'static str) -> Self {
+        MethodSignature {
+            sig,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the JNI signature string, e.g. `(Ljava/lang/String;I)I`.
+    pub const fn as_str(&self) -> &'static str {
+        self.sig
+    }
+}
+
+// Implemented manually, as deriving them would require `Args` and `Ret` to
+// implement them too.
+impl<Args, Ret> Clone for MethodSignature<Args, Ret> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args, Ret> Copy for MethodSignature<Args, Ret> {}
+
+impl<Args, Ret> fmt::Debug for MethodSignature<Args, Ret> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MethodSignature").field(&self.sig).finish()
+    }
+}
+
+impl<Args, Ret> fmt::Display for MethodSignature<Args, Ret> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.sig)
+    }
+}
+
+/// A Rust value that can be passed as a Java argument of type `T`, where `T`
+/// is one of the argument types of a `MethodSignature`.
+pub trait JavaArg<T> {
+    /// Converts the value into a raw JNI argument.
+    fn into_jvalue(self) -> jvalue;
+}
+
+/// The arguments of a call with a `MethodSignature` whose arguments are
+/// `Args`: a tuple of values implementing `JavaArg` for the respective type.
+pub trait JavaArgs<Args> {
+    /// The raw JNI arguments, an array of `jvalue`s.
+    type JValues: AsRef<[jvalue]>;
+
+    /// Converts the values into raw JNI arguments.
+    fn into_jvalues(self) -> Self::JValues;
+}
+
+/// The return type of a `MethodSignature`, which determines the type of the
+/// value returned by calls made with it.
+pub trait JavaReturn<'a> {
+    /// The type of the value returned by calls.
+    type Output;
+
+    /// The return type used to make the call.
+    fn return_type() -> ReturnType;
+
+    /// Unwraps the value returned by the call.
+    fn from_jvalue(value: JValue<'a>) -> Result<Self::Output>;
+}
+
+macro_rules! java_primitive {
+    ($ty:ty, $primitive:ident, $unwrap:ident) => {
+        impl JavaArg<$ty> for $ty {
+            fn into_jvalue(self) -> jvalue {
+                JValue::from(self).to_jni()
+            }
+        }
+
+        impl<'a> JavaReturn<'a> for $ty {
+            type Output = $ty;
+
+            fn return_type() -> ReturnType {
+                ReturnType::Primitive(Primitive::$primitive)
+            }
+
+            fn from_jvalue(value: JValue<'a>) -> Result<$ty> {
+                value.$unwrap()
+            }
+        }
+    };
+}
+
+java_primitive!(bool, Boolean, z);
+java_primitive!(jbyte, Byte, b);
+java_primitive!(jchar, Char, c);
+java_primitive!(jshort, Short, s);
+java_primitive!(jint, Int, i);
+java_primitive!(jlong, Long, j);
+java_primitive!(jfloat, Float, f);
+java_primitive!(jdouble, Double, d);
+
+impl<'a> JavaReturn<'a> for () {
+    type Output = ();
+
+    fn return_type() -> ReturnType {
+        ReturnType::Primitive(Primitive::Void)
+    }
+
+    fn from_jvalue(value: JValue<'a>) -> Result<()> {
+        value.v()
+    }
+}
+
+macro_rules! java_object {
+    ($ty:ident) => {
+        // As with `JValue`, the class of object arguments isn't checked.
+        impl<'m, 'o, T> JavaArg<$ty<'m>> for T
+        where
+            T: Into<JObject<'o>>,
+        {
+            fn into_jvalue(self) -> jvalue {
+                JValue::Object(self.into()).to_jni()
+            }
+        }
+
+        impl<'a, 'm> JavaReturn<'a> for $ty<'m> {
+            type Output = $ty<'a>;
+
+            fn return_type() -> ReturnType {
+                ReturnType::Object
+            }
+
+            fn from_jvalue(value: JValue<'a>) -> Result<$ty<'a>> {
+                value.l().map(Into::into)
+            }
+        }
+    };
+}
+
+java_object!(JObject);
+java_object!(JString);
+java_object!(JClass);
+java_object!(JThrowable);
+
+macro_rules! java_args {
+    ($count:literal $(, $arg:ident: $ty:ident)*) => {
+        impl<$($arg, $ty),*> JavaArgs<($($ty,)*)> for ($($arg,)*)
+        where
+            $($arg: JavaArg<$ty>),*
+        {
+            type JValues = [jvalue; $count];
+
+            #[allow(non_snake_case)]
+            fn into_jvalues(self) -> Self::JValues {
+                let ($($arg,)*) = self;
+                [$($arg.into_jvalue()),*]
+            }
+        }
+    };
+}
+
+java_args!(0);
+java_args!(1, A1: T1);
+java_args!(2, A1: T1, A2: T2);
+java_args!(3, A1: T1, A2: T2, A3: T3);
+java_args!(4, A1: T1, A2: T2, A3: T3, A4: T4);
+java_args!(5, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5);
+java_args!(6, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6);
+java_args!(7, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7);
+java_args!(8, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7, A8: T8);
+java_args!(9, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7, A8: T8, A9: T9);
+java_args!(10, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7, A8: T8, A9: T9, A10: T10);
+java_args!(11, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7, A8: T8, A9: T9, A10: T10, A11: T11);
+java_args!(12, A1: T1, A2: T2, A3: T3, A4: T4, A5: T5, A6: T6, A7: T7, A8: T8, A9: T9, A10: T10, A11: T11, A12: T12);
+
 fn parse_primitive<S: Stream<Token = char>>(input: &mut S) -> StdParseResult<Primitive, S>
 where
     S::Error: ParseError<char, S::Range, S::Position>,
@@ -0,0 +1,135 @@
+use std::slice;
+
+use log::warn;
+
+use crate::{
+    errors::*,
+    objects::{CriticalEnv, JString},
+    sys::{jchar, jsize},
+    JNIEnv,
+};
+
+/// Reference to the UTF-16 contents of a string in the JVM. Holds a pointer to
+/// the array returned by `GetStringChars`. Calls `ReleaseStringChars` on Drop.
+///
+/// Derefs to a `[jchar]` slice, so no transcoding takes place unless the
+/// contents are converted to a Rust `String`.
+pub struct JavaChars<'a: 'b, 'b> {
+    internal: *const jchar,
+    len: usize,
+    obj: JString<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JavaChars<'a, 'b> {
+    /// Build a `JavaChars` from an object and a reference to the environment.
+    /// You probably want to use `JNIEnv::get_string_utf16` instead.
+    pub fn from_env(env: &'b JNIEnv<'a>, obj: JString<'a>) -> Result<Self> {
+        let len = env.get_string_length(obj)? as usize;
+        let ptr = env.get_string_chars(obj)?;
+        Ok(JavaChars {
+            internal: ptr,
+            len,
+            obj,
+            env,
+        })
+    }
+
+    /// Extract the raw pointer to the UTF-16 characters.
+    pub fn get_raw(&self) -> *const jchar {
+        self.internal
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JavaChars<'a, 'b> {
+    type Target = [jchar];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.internal, self.len) }
+    }
+}
+
+impl<'a: 'b, 'b> From<JavaChars<'a, 'b>> for String {
+    fn from(other: JavaChars) -> String {
+        String::from_utf16_lossy(&other)
+    }
+}
+
+impl<'a: 'b, 'b> Drop for JavaChars<'a, 'b> {
+    fn drop(&mut self) {
+        match unsafe { self.env.release_string_chars(self.obj, self.internal) } {
+            Ok(()) => {}
+            Err(e) => warn!("error dropping java chars: {}", e),
+        }
+    }
+}
+
+/// Reference to the UTF-16 contents of a string in the JVM, obtained in a
+/// critical region. Holds a pointer to the array returned by
+/// `GetStringCritical`. Calls `ReleaseStringCritical` on Drop.
+///
+/// The JVM is more likely to hand out the string contents without copying
+/// them, but while this value is alive the current thread **must not** call
+/// any other JNI functions than the ones of the `CriticalEnv` handle (see
+/// `split_env`), nor block. Keep it alive for as short as possible, e.g. with
+/// a nested scope or an explicit `std::mem::drop`.
+///
+/// See the [JNI documentation][spec] for details.
+///
+/// [spec]: https://docs.oracle.com/en/java/javase/11/docs/specs/jni/functions.html#getstringcritical-releasestringcritical
+pub struct JavaCharsCritical<'a: 'b, 'b> {
+    internal: *const jchar,
+    len: usize,
+    obj: JString<'a>,
+    env: &'b JNIEnv<'a>,
+}
+
+impl<'a: 'b, 'b> JavaCharsCritical<'a, 'b> {
+    // Build a `JavaCharsCritical` from an object and a reference to the
+    // environment, see `JNIEnv::get_string_utf16_critical`.
+    pub(crate) fn from_env(env: &'b JNIEnv<'a>, obj: JString<'a>) -> Result<Self> {
+        // The length must be queried before entering the critical region.
+        let len = env.get_string_length(obj)?;
+        Self::new(env, obj, len)
+    }
+
+    pub(crate) fn new(env: &'b JNIEnv<'a>, obj: JString<'a>, len: jsize) -> Result<Self> {
+        let ptr = unsafe { env.get_string_critical(obj)? };
+        Ok(JavaCharsCritical {
+            internal: ptr,
+            len: len as usize,
+            obj,
+            env,
+        })
+    }
+
+    /// Extract the raw pointer to the UTF-16 characters.
+    pub fn get_raw(&self) -> *const jchar {
+        self.internal
+    }
+
+    /// Get the UTF-16 characters, along with a `CriticalEnv` handle to call
+    /// the JNI functions that are allowed while the string is held, e.g. to
+    /// get a critical array to copy the characters to.
+    pub fn split_env(&self) -> (&[jchar], CriticalEnv<'a, '_>) {
+        let chars = unsafe { slice::from_raw_parts(self.internal, self.len) };
+        (chars, CriticalEnv::new(self.env))
+    }
+}
+
+impl<'a: 'b, 'b> ::std::ops::Deref for JavaCharsCritical<'a, 'b> {
+    type Target = [jchar];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.internal, self.len) }
+    }
+}
+
+impl<'a: 'b, 'b> Drop for JavaCharsCritical<'a, 'b> {
+    fn drop(&mut self) {
+        match unsafe { self.env.release_string_critical(self.obj, self.internal) } {
+            Ok(()) => {}
+            Err(e) => warn!("error dropping critical java chars: {}", e),
+        }
+    }
+}
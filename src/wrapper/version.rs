@@ -1,10 +1,16 @@
+use std::cmp::Ordering;
+
 use crate::sys::{
-    JNI_VERSION_1_1, JNI_VERSION_1_2, JNI_VERSION_1_4, JNI_VERSION_1_6, JNI_VERSION_1_8,
+    JNI_VERSION_10, JNI_VERSION_19, JNI_VERSION_1_1, JNI_VERSION_1_2, JNI_VERSION_1_4,
+    JNI_VERSION_1_6, JNI_VERSION_1_8, JNI_VERSION_20, JNI_VERSION_21, JNI_VERSION_9,
 };
 
 /// JNI Version
 ///
-/// This maps to the `jni_sys::JNI_VERSION_1_*` constants.
+/// This maps to the `jni_sys::JNI_VERSION_*` constants.
+///
+/// Versions are ordered by their numeric value, so they can be compared for
+/// feature gating, e.g. `env.get_version()? >= JNIVersion::V9`.
 #[derive(Debug, Copy, Clone)]
 #[allow(missing_docs)]
 pub enum JNIVersion {
@@ -13,6 +19,11 @@ pub enum JNIVersion {
     V4,
     V6,
     V8,
+    V9,
+    V10,
+    V19,
+    V20,
+    V21,
     Invalid(i32),
 }
 
@@ -24,6 +35,11 @@ impl From<i32> for JNIVersion {
             JNI_VERSION_1_4 => JNIVersion::V4,
             JNI_VERSION_1_6 => JNIVersion::V6,
             JNI_VERSION_1_8 => JNIVersion::V8,
+            JNI_VERSION_9 => JNIVersion::V9,
+            JNI_VERSION_10 => JNIVersion::V10,
+            JNI_VERSION_19 => JNIVersion::V19,
+            JNI_VERSION_20 => JNIVersion::V20,
+            JNI_VERSION_21 => JNIVersion::V21,
             v => JNIVersion::Invalid(v),
         }
     }
@@ -37,7 +53,66 @@ impl From<JNIVersion> for i32 {
             JNIVersion::V4 => JNI_VERSION_1_4,
             JNIVersion::V6 => JNI_VERSION_1_6,
             JNIVersion::V8 => JNI_VERSION_1_8,
+            JNIVersion::V9 => JNI_VERSION_9,
+            JNIVersion::V10 => JNI_VERSION_10,
+            JNIVersion::V19 => JNI_VERSION_19,
+            JNIVersion::V20 => JNI_VERSION_20,
+            JNIVersion::V21 => JNI_VERSION_21,
             JNIVersion::Invalid(v) => v,
         }
     }
 }
+
+impl PartialEq for JNIVersion {
+    fn eq(&self, other: &Self) -> bool {
+        i32::from(*self) == i32::from(*other)
+    }
+}
+
+impl Eq for JNIVersion {}
+
+impl PartialOrd for JNIVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JNIVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        i32::from(*self).cmp(&i32::from(*other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_version_roundtrip() {
+        for v in &[
+            JNIVersion::V1,
+            JNIVersion::V2,
+            JNIVersion::V4,
+            JNIVersion::V6,
+            JNIVersion::V8,
+            JNIVersion::V9,
+            JNIVersion::V10,
+            JNIVersion::V19,
+            JNIVersion::V20,
+            JNIVersion::V21,
+        ] {
+            let raw: i32 = (*v).into();
+            assert_eq!(JNIVersion::from(raw), *v);
+            assert!(!matches!(JNIVersion::from(raw), JNIVersion::Invalid(_)));
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(JNIVersion::V1 < JNIVersion::V8);
+        assert!(JNIVersion::V8 < JNIVersion::V9);
+        assert!(JNIVersion::V10 < JNIVersion::V19);
+        assert!(JNIVersion::V21 >= JNIVersion::V9);
+        assert_eq!(JNIVersion::Invalid(JNI_VERSION_9), JNIVersion::V9);
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "invocation")]
+
+use std::fmt::Debug;
+
+use jni::{
+    errors::Error,
+    objects::{Boxable, JList, JObject},
+    sys::{jbyte, jchar, jdouble, jfloat, jint, jlong, jshort},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn assert_roundtrip<T: Boxable + Copy + PartialEq + Debug>(env: &JNIEnv, value: T) {
+    let boxed = unwrap(env, env.box_value(value));
+    assert!(unwrap(env, env.is_instance_of(boxed, T::CLASS_NAME)));
+    assert_eq!(unwrap(env, env.unbox_value::<T>(boxed)), value);
+}
+
+#[test]
+pub fn box_and_unbox_primitives() {
+    let env = attach_current_thread();
+
+    assert_roundtrip(&env, true);
+    assert_roundtrip(&env, false);
+    assert_roundtrip::<jbyte>(&env, -42);
+    assert_roundtrip::<jchar>(&env, 0xe9);
+    assert_roundtrip::<jshort>(&env, jshort::MIN);
+    assert_roundtrip::<jint>(&env, jint::MAX);
+    assert_roundtrip::<jlong>(&env, jlong::MIN);
+    assert_roundtrip::<jfloat>(&env, 1.5);
+    assert_roundtrip::<jdouble>(&env, -0.25);
+}
+
+#[test]
+pub fn boxed_values_in_collections() {
+    let env = attach_current_thread();
+    let list = unwrap(&env, JList::new_array_list(&env, 3));
+    for i in 0..3 {
+        unwrap(&env, list.add(unwrap(&env, env.box_value(i * 10))));
+    }
+
+    let values: Vec<jint> = unwrap(&env, list.iter())
+        .map(|obj| unwrap(&env, env.unbox_value(unwrap(&env, obj).as_obj())))
+        .collect();
+    assert_eq!(values, [0, 10, 20]);
+
+    // Boxed values are equal to Java ones.
+    let parsed = unwrap(
+        &env,
+        env.call_static_method(
+            "java/lang/Integer",
+            "valueOf",
+            "(Ljava/lang/String;)Ljava/lang/Integer;",
+            &[unwrap(&env, env.new_string("20")).into()],
+        ),
+    );
+    assert!(unwrap(&env, list.contains(unwrap(&env, parsed.l()))));
+}
+
+#[test]
+pub fn unbox_errors() {
+    let env = attach_current_thread();
+
+    match env.unbox_value::<jint>(JObject::null()) {
+        Err(Error::NullPtr(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let long = unwrap(&env, env.box_value(1 as jlong));
+    match env.unbox_value::<jint>(long) {
+        Err(Error::WrongObjectType("java.lang.Integer")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(!unwrap(&env, env.exception_check()));
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "invocation")]
+
+use std::collections::HashMap;
+
+use jni::{
+    convert::{FromJava, IntoJava},
+    errors::Error,
+    objects::JObject,
+    sys::{jint, jlong},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn to_string(env: &JNIEnv, obj: JObject) -> String {
+    let s = unwrap(
+        env,
+        env.call_method(obj, "toString", "()Ljava/lang/String;", &[]),
+    );
+    unwrap(env, env.get_string(unwrap(env, s.l()).into())).into()
+}
+
+#[test]
+pub fn convert_std_types_to_java() {
+    let env = attach_current_thread();
+
+    let s = unwrap(&env, "hello".into_java(&env));
+    assert!(unwrap(&env, env.is_instance_of(s, "java/lang/String")));
+    assert_eq!(to_string(&env, s), "hello");
+
+    let n = unwrap(&env, 42.into_java(&env));
+    assert!(unwrap(&env, env.is_instance_of(n, "java/lang/Integer")));
+
+    assert!(unwrap(&env, None::<String>.into_java(&env)).is_null());
+
+    let list = unwrap(&env, vec![Some(1), None, Some(3)].into_java(&env));
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(list, "java/util/ArrayList")
+    ));
+    assert_eq!(to_string(&env, list), "[1, null, 3]");
+
+    let mut map = HashMap::new();
+    map.insert("key".to_string(), vec![true, false]);
+    let map = unwrap(&env, map.into_java(&env));
+    assert!(unwrap(&env, env.is_instance_of(map, "java/util/HashMap")));
+    assert_eq!(to_string(&env, map), "{key=[true, false]}");
+
+    let tuple = unwrap(&env, ("a", 1, (None::<String>, true)).into_java(&env));
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(tuple, "java/util/ArrayList")
+    ));
+    assert_eq!(to_string(&env, tuple), "[a, 1, [null, true]]");
+}
+
+#[test]
+pub fn convert_java_to_std_types() {
+    let env = attach_current_thread();
+
+    let list = unwrap(
+        &env,
+        vec![vec!["a".to_string()], vec![], vec!["b".into(), "c".into()]].into_java(&env),
+    );
+    let strings: Vec<Vec<String>> = unwrap(&env, FromJava::from_java(&env, list));
+    assert_eq!(strings, [vec!["a"], vec![], vec!["b", "c"]]);
+
+    let mut map = HashMap::new();
+    map.insert(1 as jlong, Some("one".to_string()));
+    map.insert(2, None);
+    let java_map = unwrap(&env, map.clone().into_java(&env));
+    let converted: HashMap<jlong, Option<String>> =
+        unwrap(&env, FromJava::from_java(&env, java_map));
+    assert_eq!(converted, map);
+
+    let none: Option<jint> = unwrap(&env, FromJava::from_java(&env, JObject::null()));
+    assert_eq!(none, None);
+
+    let tuple = unwrap(&env, ("x".to_string(), vec![2 as jlong]).into_java(&env));
+    let converted: (String, Vec<jlong>) = unwrap(&env, FromJava::from_java(&env, tuple));
+    assert_eq!(converted, ("x".to_string(), vec![2]));
+}
+
+#[test]
+pub fn convert_errors() {
+    let env = attach_current_thread();
+
+    match String::from_java(&env, JObject::null()) {
+        Err(Error::NullPtr(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let n = unwrap(&env, 1.into_java(&env));
+    match String::from_java(&env, n) {
+        Err(Error::WrongObjectType("java.lang.String")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    match Vec::<jint>::from_java(&env, n) {
+        Err(Error::WrongObjectType("java.util.Collection")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    match HashMap::<jint, jint>::from_java(&env, n) {
+        Err(Error::WrongObjectType("java.util.Map")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let list = unwrap(&env, vec!["a"].into_java(&env));
+    match Vec::<jint>::from_java(&env, list) {
+        Err(Error::WrongObjectType("java.lang.Integer")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    match <(String, String)>::from_java(&env, list) {
+        Err(Error::Conversion(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(!unwrap(&env, env.exception_check()));
+}
+
+#[test]
+pub fn call_with_converted_args() {
+    let env = attach_current_thread();
+
+    let list = unwrap(
+        &env,
+        env.new_object_converted(
+            "java/util/ArrayList",
+            "(Ljava/util/Collection;)V",
+            (vec!["a", "b"],),
+        ),
+    );
+    unwrap(
+        &env,
+        env.call_method_converted(list, "add", "(Ljava/lang/Object;)Z", ("c",)),
+    );
+
+    let joined = unwrap(
+        &env,
+        env.call_static_method_converted(
+            "java/lang/String",
+            "join",
+            "(Ljava/lang/CharSequence;Ljava/lang/Iterable;)Ljava/lang/String;",
+            (", ", list),
+        ),
+    );
+    assert_eq!(to_string(&env, unwrap(&env, joined.l())), "a, b, c");
+
+    let empty = unwrap(
+        &env,
+        env.new_object_converted("java/util/HashMap", "()V", ()),
+    );
+    assert_eq!(to_string(&env, empty), "{}");
+}
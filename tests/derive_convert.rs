@@ -0,0 +1,133 @@
+#![cfg(all(feature = "invocation", feature = "macros"))]
+
+use jni::{
+    convert::{FromJava, IntoJava},
+    errors::Error,
+    objects::JObject,
+    sys::jint,
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+#[derive(Debug, PartialEq, FromJava, IntoJava)]
+#[jni(class = "java.awt.Point")]
+struct Point {
+    x: jint,
+    #[jni(rename = "y")]
+    vertical: jint,
+}
+
+#[derive(Debug, PartialEq, FromJava, IntoJava)]
+#[jni(class = "java/util/AbstractMap$SimpleImmutableEntry", record)]
+struct Entry {
+    #[jni(rename = "getKey", signature = "Ljava/lang/Object;")]
+    key: String,
+    #[jni(rename = "getValue", signature = "Ljava/lang/Object;")]
+    value: Option<Point>,
+}
+
+#[derive(Debug, PartialEq, FromJava, IntoJava)]
+#[jni(class = "java.time.DayOfWeek")]
+enum Day {
+    #[jni(rename = "MONDAY")]
+    Monday,
+    #[jni(rename = "SATURDAY")]
+    Saturday,
+}
+
+fn to_string(env: &JNIEnv, obj: JObject) -> String {
+    let s = unwrap(
+        env,
+        env.call_method(obj, "toString", "()Ljava/lang/String;", &[]),
+    );
+    unwrap(env, env.get_string(unwrap(env, s.l()).into())).into()
+}
+
+#[test]
+pub fn derive_struct_fields() {
+    let env = attach_current_thread();
+
+    let point = Point { x: 1, vertical: -2 };
+    let obj = unwrap(&env, point.into_java(&env));
+    assert_eq!(to_string(&env, obj), "java.awt.Point[x=1,y=-2]");
+
+    unwrap(
+        &env,
+        env.call_method(obj, "translate", "(II)V", &[10.into(), 20.into()]),
+    );
+    assert_eq!(
+        unwrap(&env, Point::from_java(&env, obj)),
+        Point {
+            x: 11,
+            vertical: 18
+        }
+    );
+}
+
+#[test]
+pub fn derive_struct_record() {
+    let env = attach_current_thread();
+
+    let entry = Entry {
+        key: "origin".to_string(),
+        value: Some(Point { x: 0, vertical: 0 }),
+    };
+    let obj = unwrap(&env, entry.into_java(&env));
+    assert_eq!(to_string(&env, obj), "origin=java.awt.Point[x=0,y=0]");
+    assert_eq!(
+        unwrap(&env, Entry::from_java(&env, obj)),
+        Entry {
+            key: "origin".to_string(),
+            value: Some(Point { x: 0, vertical: 0 }),
+        }
+    );
+
+    let empty = Entry {
+        key: "none".to_string(),
+        value: None,
+    };
+    let obj = unwrap(&env, empty.into_java(&env));
+    assert_eq!(unwrap(&env, Entry::from_java(&env, obj)).value, None);
+}
+
+#[test]
+pub fn derive_enum() {
+    let env = attach_current_thread();
+
+    let obj = unwrap(&env, Day::Saturday.into_java(&env));
+    assert_eq!(to_string(&env, obj), "SATURDAY");
+    assert_eq!(unwrap(&env, Day::from_java(&env, obj)), Day::Saturday);
+
+    let sunday = unwrap(
+        &env,
+        env.get_static_field("java/time/DayOfWeek", "SUNDAY", "Ljava/time/DayOfWeek;"),
+    );
+    match Day::from_java(&env, unwrap(&env, sunday.l())) {
+        Err(Error::Conversion(msg)) => {
+            assert_eq!(
+                msg,
+                "no variant of java.time.DayOfWeek for the constant SUNDAY"
+            )
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+pub fn derive_errors() {
+    let env = attach_current_thread();
+
+    match Point::from_java(&env, JObject::null()) {
+        Err(Error::NullPtr(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let day = unwrap(&env, Day::Monday.into_java(&env));
+    match Point::from_java(&env, day) {
+        Err(Error::WrongObjectType("java.awt.Point")) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(!unwrap(&env, env.exception_check()));
+}
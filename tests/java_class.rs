@@ -0,0 +1,131 @@
+#![cfg(feature = "invocation")]
+
+use jni::{
+    errors::Error,
+    java_class,
+    objects::{JObject, JString},
+    sys::jint,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+java_class! {
+    /// A `java.util.concurrent.atomic.AtomicInteger`.
+    pub class AtomicInteger = "java/util/concurrent/atomic/AtomicInteger" {
+        pub constructor new(initial_value: jint) = "(I)V";
+        pub constructor new_zero() = "()V";
+        pub fn get() -> jint = "get", "()I";
+        pub fn add_and_get(delta: jint) -> jint = "addAndGet", "(I)I";
+        pub fn compare_and_set(expected: jint, new_value: jint) -> bool = "compareAndSet", "(II)Z";
+        pub fn set(value: jint) = "set", "(I)V";
+        pub fn to_string() -> JString<'a> = "toString", "()Ljava/lang/String;";
+        field value, set_value: jint = "value", "I";
+    }
+}
+
+java_class! {
+    class Integer = "java/lang/Integer" {
+        static fn parse_int(s: JString<'a>) -> jint = "parseInt", "(Ljava/lang/String;)I";
+        static fn value_of(value: jint) -> JObject<'a> = "valueOf", "(I)Ljava/lang/Integer;";
+        static field max_value: jint = "MAX_VALUE", "I";
+        // Wrong declarations, for testing the signature checks.
+        static fn bad_arg_count() -> jint = "parseInt", "(Ljava/lang/String;)I";
+        static fn bad_return(s: JString<'a>) -> JObject<'a> = "parseInt", "(Ljava/lang/String;)I";
+        static fn bad_arg(s: jint) -> jint = "parseInt", "(Ljava/lang/String;)I";
+        static field bad_field: bool = "MAX_VALUE", "I";
+    }
+}
+
+java_class! {
+    class StringBuilder = "java/lang/StringBuilder" {
+        constructor new(s: JString<'a>) = "(Ljava/lang/String;)V";
+        constructor bad(s: JString<'a>) = "(Ljava/lang/String;)I";
+        constructor bad_arg(capacity: bool) = "(I)V";
+        fn append(s: JObject<'a>) -> JObject<'a> = "append", "(Ljava/lang/Object;)Ljava/lang/StringBuilder;";
+        fn length() -> jint = "length", "()I";
+    }
+}
+
+#[test]
+fn java_class_calls_methods() {
+    let env = attach_current_thread();
+
+    let counter = unwrap(&env, AtomicInteger::new(&env, 41));
+    assert_eq!(unwrap(&env, counter.get()), 41);
+    assert_eq!(unwrap(&env, counter.add_and_get(1)), 42);
+    assert!(unwrap(&env, counter.compare_and_set(42, 7)));
+    assert!(!unwrap(&env, counter.compare_and_set(42, 8)));
+
+    unwrap(&env, counter.set(3));
+    let s = unwrap(&env, counter.to_string());
+    let s: String = unwrap(&env, env.get_string(s)).into();
+    assert_eq!(s, "3");
+
+    let zero = unwrap(&env, AtomicInteger::new_zero(&env));
+    assert_eq!(unwrap(&env, zero.get()), 0);
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(*zero, AtomicInteger::CLASS_NAME)
+    ));
+}
+
+#[test]
+fn java_class_accesses_fields() {
+    let env = attach_current_thread();
+
+    let counter = unwrap(&env, AtomicInteger::new(&env, 5));
+    assert_eq!(unwrap(&env, counter.value()), 5);
+    unwrap(&env, counter.set_value(6));
+    assert_eq!(unwrap(&env, counter.get()), 6);
+
+    assert_eq!(unwrap(&env, Integer::max_value(&env)), i32::MAX);
+}
+
+#[test]
+fn java_class_calls_static_methods() {
+    let env = attach_current_thread();
+
+    let s = unwrap(&env, env.new_string("-12"));
+    assert_eq!(unwrap(&env, Integer::parse_int(&env, s)), -12);
+
+    let boxed = unwrap(&env, Integer::value_of(&env, 12));
+    assert!(unwrap(&env, env.is_instance_of(boxed, "java/lang/Integer")));
+
+    let initial = unwrap(&env, env.new_string("abc"));
+    let builder = unwrap(&env, StringBuilder::new(&env, initial));
+    let returned = unwrap(&env, builder.append(boxed));
+    assert!(unwrap(&env, env.is_same_object(returned, *builder)));
+    assert_eq!(unwrap(&env, builder.length()), 5);
+}
+
+#[test]
+fn java_class_checks_signatures() {
+    let env = attach_current_thread();
+
+    assert!(matches!(
+        Integer::bad_arg_count(&env),
+        Err(Error::InvalidArgList(_))
+    ));
+    let s = unwrap(&env, env.new_string("1"));
+    assert!(matches!(
+        Integer::bad_return(&env, s),
+        Err(Error::WrongJValueType("object", "int"))
+    ));
+    assert!(matches!(
+        Integer::bad_arg(&env, 1),
+        Err(Error::WrongJValueType("int", "object"))
+    ));
+    assert!(matches!(
+        Integer::bad_field(&env),
+        Err(Error::WrongJValueType("bool", "int"))
+    ));
+    assert!(matches!(
+        StringBuilder::bad(&env, s),
+        Err(Error::InvalidCtorReturn)
+    ));
+    assert!(matches!(
+        StringBuilder::bad_arg(&env, true),
+        Err(Error::WrongJValueType("bool", "int"))
+    ));
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "invocation")]
+
+use jni::{
+    objects::{JObject, JValue},
+    Executor, JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, jvm, unwrap};
+
+// An empty `public class HiddenFromSystem`, which is only visible through the
+// class loader it is defined with.
+const HIDDEN_CLASS: &[u8] = &[
+    0xca, 0xfe, 0xba, 0xbe, // magic
+    0x00, 0x00, 0x00, 0x34, // version 52.0
+    0x00, 0x05, // constant pool count
+    0x07, 0x00, 0x02, // #1 = Class #2
+    0x01, 0x00, 0x10, b'H', b'i', b'd', b'd', b'e', b'n', b'F', b'r', b'o', b'm', b'S', b'y', b's',
+    b't', b'e', b'm', // #2 = Utf8 HiddenFromSystem
+    0x07, 0x00, 0x04, // #3 = Class #4
+    0x01, 0x00, 0x10, b'j', b'a', b'v', b'a', b'/', b'l', b'a', b'n', b'g', b'/', b'O', b'b', b'j',
+    b'e', b'c', b't', // #4 = Utf8 java/lang/Object
+    0x00, 0x21, // ACC_PUBLIC | ACC_SUPER
+    0x00, 0x01, // this class
+    0x00, 0x03, // super class
+    0x00, 0x00, // interfaces
+    0x00, 0x00, // fields
+    0x00, 0x00, // methods
+    0x00, 0x00, // attributes
+];
+
+fn new_class_loader<'a>(env: &JNIEnv<'a>) -> JObject<'a> {
+    let urls = unwrap(
+        env,
+        env.new_object_array(0, "java/net/URL", JObject::null()),
+    );
+    let loader = unwrap(
+        env,
+        env.new_object(
+            "java/net/URLClassLoader",
+            "([Ljava/net/URL;)V",
+            &[JValue::from(urls)],
+        ),
+    );
+    unwrap(
+        env,
+        env.define_class("HiddenFromSystem", loader, HIDDEN_CLASS),
+    );
+    loader
+}
+
+#[test]
+fn find_class_with_loader() {
+    let env = attach_current_thread();
+    let loader = new_class_loader(&env);
+
+    assert!(env.find_class("HiddenFromSystem").is_err());
+    unwrap(&env, env.exception_clear());
+
+    let class = unwrap(&env, env.find_class_with_loader("HiddenFromSystem", loader));
+    let class_loader = unwrap(&env, env.get_class_loader(class));
+    assert!(unwrap(&env, env.is_same_object(class_loader, loader)));
+
+    // Classes of the parent class loaders are found too.
+    let class = unwrap(&env, env.find_class_with_loader("java/lang/String", loader));
+    let string_class = unwrap(&env, env.find_class("java/lang/String"));
+    assert!(unwrap(&env, env.is_same_object(class, string_class)));
+    assert!(unwrap(&env, env.get_class_loader(class)).is_null());
+
+    let res = env.find_class_with_loader("NoSuchClass", loader);
+    assert!(res.is_err());
+    let exception = unwrap(&env, env.exception_occurred());
+    unwrap(&env, env.exception_clear());
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(exception, "java/lang/ClassNotFoundException")
+    ));
+}
+
+#[test]
+fn find_class_with_null_loader_and_arrays() {
+    let env = attach_current_thread();
+
+    let class = unwrap(
+        &env,
+        env.find_class_with_loader("java.lang.Integer", JObject::null()),
+    );
+    let integer_class = unwrap(&env, env.find_class("java/lang/Integer"));
+    assert!(unwrap(&env, env.is_same_object(class, integer_class)));
+
+    let loader = new_class_loader(&env);
+    let class = unwrap(
+        &env,
+        env.find_class_with_loader("[LHiddenFromSystem;", loader),
+    );
+    let name = unwrap(
+        &env,
+        env.call_method(class, "getName", "()Ljava/lang/String;", &[]),
+    );
+    let name: String = unwrap(&env, env.get_string(unwrap(&env, name.l()).into())).into();
+    assert_eq!(name, "[LHiddenFromSystem;");
+}
+
+#[test]
+fn context_class_loader() {
+    let env = attach_current_thread();
+    let loader = new_class_loader(&env);
+
+    let thread = unwrap(
+        &env,
+        env.call_static_method(
+            "java/lang/Thread",
+            "currentThread",
+            "()Ljava/lang/Thread;",
+            &[],
+        ),
+    );
+    let thread = unwrap(&env, thread.l());
+    let previous = unwrap(&env, env.get_context_class_loader());
+    let set_context_class_loader = |loader: JObject| {
+        unwrap(
+            &env,
+            env.call_method(
+                thread,
+                "setContextClassLoader",
+                "(Ljava/lang/ClassLoader;)V",
+                &[JValue::from(loader)],
+            ),
+        );
+    };
+
+    set_context_class_loader(loader);
+    let context_loader = unwrap(&env, env.get_context_class_loader());
+    assert!(unwrap(&env, env.is_same_object(context_loader, loader)));
+    unwrap(
+        &env,
+        env.find_class_with_loader("HiddenFromSystem", context_loader),
+    );
+    set_context_class_loader(previous);
+}
+
+#[test]
+fn executor_with_class_loader() {
+    let loader = {
+        let env = attach_current_thread();
+        let loader = new_class_loader(&env);
+        unwrap(&env, env.new_global_ref(loader))
+    };
+
+    let executor = Executor::new_with_class_loader(jvm().clone(), loader.clone());
+    executor
+        .with_attached(|env| {
+            let class = env.find_class("HiddenFromSystem")?;
+            let class_loader = env.get_class_loader(class)?;
+            assert!(env.is_same_object(class_loader, loader.as_obj())?);
+            Ok(())
+        })
+        .unwrap();
+
+    // The class loader is only used within the closures of the executor.
+    let env = attach_current_thread();
+    assert!(env.find_class("HiddenFromSystem").is_err());
+    unwrap(&env, env.exception_clear());
+}
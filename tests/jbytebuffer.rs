@@ -0,0 +1,117 @@
+#![cfg(feature = "invocation")]
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use jni::{errors::Error, objects::JByteBuffer, JNIEnv};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn allocate<'a>(env: &JNIEnv<'a>, capacity: i32) -> JByteBuffer<'a> {
+    let buf = unwrap(
+        env,
+        env.call_static_method(
+            "java/nio/ByteBuffer",
+            "allocate",
+            "(I)Ljava/nio/ByteBuffer;",
+            &[capacity.into()],
+        ),
+    );
+    unwrap(env, buf.l()).into()
+}
+
+#[test]
+pub fn direct_buffer_stream() {
+    let env = attach_current_thread();
+    let mut vec = vec![0u8; 8];
+    let buf = unwrap(&env, env.new_direct_byte_buffer(&mut vec));
+
+    let mut stream = unwrap(&env, env.get_byte_buffer_stream(buf));
+    assert_eq!(unwrap(&env, stream.capacity()), 8);
+    stream.write_all(b"hello").unwrap();
+    assert_eq!(unwrap(&env, stream.position()), 5);
+    assert_eq!(unwrap(&env, stream.remaining()), 3);
+    assert_eq!(stream.write(b"world").unwrap(), 3);
+    assert_eq!(stream.write(b"!").unwrap(), 0);
+
+    assert_eq!(stream.seek(SeekFrom::Start(1)).unwrap(), 1);
+    let mut read = [0; 4];
+    stream.read_exact(&mut read).unwrap();
+    assert_eq!(&read, b"ello");
+
+    assert_eq!(vec, b"hellowor");
+}
+
+#[test]
+pub fn heap_buffer_stream() {
+    let env = attach_current_thread();
+    let buf = allocate(&env, 8);
+
+    let mut stream = unwrap(&env, env.get_byte_buffer_stream(buf));
+    stream.write_all(b"abcdef").unwrap();
+    unwrap(&env, stream.set_limit(4));
+    assert_eq!(unwrap(&env, stream.position()), 4);
+    assert_eq!(stream.write(b"g").unwrap(), 0);
+
+    assert_eq!(stream.seek(SeekFrom::End(-3)).unwrap(), 1);
+    let mut read = vec![];
+    stream.read_to_end(&mut read).unwrap();
+    assert_eq!(read, b"bcd");
+
+    // The bytes were written to the Java array of the buffer.
+    let array = unwrap(&env, env.call_method(buf, "array", "()[B", &[]));
+    let array = unwrap(&env, env.convert_byte_array(unwrap(&env, array.l()).into()));
+    assert_eq!(array, b"abcdef\0\0");
+}
+
+#[test]
+pub fn read_only_buffer_stream() {
+    let env = attach_current_thread();
+    let buf = allocate(&env, 4);
+    unwrap(&env, env.get_byte_buffer_stream(buf))
+        .write_all(b"data")
+        .unwrap();
+    let read_only = unwrap(
+        &env,
+        env.call_method(buf, "asReadOnlyBuffer", "()Ljava/nio/ByteBuffer;", &[]),
+    );
+    let read_only = JByteBuffer::from(unwrap(&env, read_only.l()));
+
+    let mut stream = unwrap(&env, env.get_byte_buffer_stream(read_only));
+    assert!(stream.is_read_only());
+    unwrap(&env, stream.set_position(0));
+    let mut read = String::new();
+    stream.read_to_string(&mut read).unwrap();
+    assert_eq!(read, "data");
+
+    unwrap(&env, stream.set_position(0));
+    match stream.write_bytes(b"x") {
+        Err(Error::JavaException) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(unwrap(&env, env.exception_check()));
+    unwrap(&env, env.exception_clear());
+}
+
+#[test]
+pub fn byte_buffer_stream_errors() {
+    let env = attach_current_thread();
+
+    let string = unwrap(&env, env.new_string("wrong"));
+    match env.get_byte_buffer_stream(JByteBuffer::from(string.into_inner())) {
+        Err(Error::WrongObjectType("java.nio.ByteBuffer")) => {}
+        res => panic!("unexpected result: {:?}", res.map(|_| ())),
+    }
+
+    let mut stream = unwrap(&env, env.get_byte_buffer_stream(allocate(&env, 4)));
+    assert_eq!(
+        stream.seek(SeekFrom::Current(5)).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        stream.seek(SeekFrom::End(-5)).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+    assert_eq!(unwrap(&env, stream.position()), 0);
+    assert!(!unwrap(&env, env.exception_check()));
+}
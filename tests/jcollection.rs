@@ -0,0 +1,73 @@
+#![cfg(feature = "invocation")]
+
+use jni::{
+    errors::Error,
+    objects::{JIterator, JObject, JSet},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn sorted_strings(env: &JNIEnv, iter: JIterator) -> Vec<String> {
+    let mut strings: Vec<String> = iter
+        .map(|elem| unwrap(env, env.get_string(elem.as_obj().into())).into())
+        .collect();
+    strings.sort_unstable();
+    strings
+}
+
+#[test]
+pub fn jset_add_remove_contains() {
+    let env = attach_current_thread();
+    let set = unwrap(&env, JSet::new_hash_set(&env));
+    let a: JObject = unwrap(&env, env.new_string("a")).into();
+    let b: JObject = unwrap(&env, env.new_string("b")).into();
+    let other_a: JObject = unwrap(&env, env.new_string("a")).into();
+
+    assert!(unwrap(&env, set.is_empty()));
+    assert!(unwrap(&env, set.add(a)));
+    assert!(unwrap(&env, set.add(b)));
+    // Elements are compared with `equals`.
+    assert!(!unwrap(&env, set.add(other_a)));
+    assert_eq!(unwrap(&env, set.size()), 2);
+    assert!(unwrap(&env, set.contains(other_a)));
+    assert_eq!(sorted_strings(&env, unwrap(&env, set.iter())), ["a", "b"]);
+
+    assert!(unwrap(&env, set.remove(other_a)));
+    assert!(!unwrap(&env, set.remove(other_a)));
+    assert!(!unwrap(&env, set.contains(a)));
+    assert_eq!(unwrap(&env, set.size()), 1);
+
+    assert!(unwrap(&env, set.add_all(vec![a, b])));
+    assert_eq!(unwrap(&env, set.size()), 2);
+    unwrap(&env, set.clear());
+    assert!(unwrap(&env, set.is_empty()));
+}
+
+#[test]
+pub fn get_collection_and_set_check_type() {
+    let env = attach_current_thread();
+    let list = unwrap(&env, env.new_object("java/util/ArrayList", "()V", &[]));
+    let set = unwrap(&env, env.new_object("java/util/TreeSet", "()V", &[]));
+    let string = unwrap(&env, env.new_string("not a collection"));
+
+    let collection = unwrap(&env, env.get_collection(list));
+    let elem = unwrap(&env, env.new_string("x")).into();
+    assert!(unwrap(&env, collection.add(elem)));
+    assert!(unwrap(&env, collection.add(elem)));
+    assert_eq!(unwrap(&env, collection.size()), 2);
+
+    unwrap(&env, env.get_collection(set));
+    unwrap(&env, env.get_set(set));
+
+    assert!(matches!(
+        env.get_set(list),
+        Err(Error::WrongObjectType("java.util.Set"))
+    ));
+    assert!(matches!(
+        env.get_collection(string.into()),
+        Err(Error::WrongObjectType("java.util.Collection"))
+    ));
+    assert!(!unwrap(&env, env.exception_check()));
+}
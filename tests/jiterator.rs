@@ -0,0 +1,116 @@
+#![cfg(feature = "invocation")]
+
+use jni::{
+    objects::{JIterable, JList, JObject},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn new_list<'a: 'b, 'b>(env: &'b JNIEnv<'a>, data: &[&str]) -> JList<'a, 'b> {
+    let strings: Vec<JObject> = data
+        .iter()
+        .map(|s| unwrap(env, env.new_string(s)).into())
+        .collect();
+    unwrap(env, JList::from_objects(env, strings))
+}
+
+fn collect_strings(env: &JNIEnv, iterable: &JIterable) -> Vec<String> {
+    unwrap(env, iterable.iter())
+        .map(|elem| unwrap(env, env.get_string(elem.as_obj().into())).into())
+        .collect()
+}
+
+#[test]
+pub fn iterate_collections() {
+    let env = attach_current_thread();
+    let data = ["hello", "world", "from", "test"];
+    let list = new_list(&env, &data);
+
+    let iterable = unwrap(&env, env.get_iterable(*list));
+    assert_eq!(collect_strings(&env, &iterable), data);
+    // Each call to `iter` starts a new iteration.
+    assert_eq!(collect_strings(&env, &iterable), data);
+
+    let set = unwrap(
+        &env,
+        env.new_object(
+            "java/util/TreeSet",
+            "(Ljava/util/Collection;)V",
+            &[(*list).into()],
+        ),
+    );
+    let iterable = unwrap(&env, env.get_iterable(set));
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable();
+    assert_eq!(collect_strings(&env, &iterable), sorted);
+}
+
+#[test]
+pub fn iterate_streams_and_iterators() {
+    let env = attach_current_thread();
+    let list = new_list(&env, &["a", "b", "c"]);
+
+    let stream = unwrap(
+        &env,
+        env.call_method(*list, "stream", "()Ljava/util/stream/Stream;", &[]),
+    );
+    let stream = unwrap(&env, stream.l());
+    let skipped = unwrap(
+        &env,
+        env.call_method(
+            stream,
+            "skip",
+            "(J)Ljava/util/stream/Stream;",
+            &[1i64.into()],
+        ),
+    );
+    let iterable = unwrap(&env, env.get_iterable(unwrap(&env, skipped.l())));
+    assert_eq!(collect_strings(&env, &iterable), ["b", "c"]);
+
+    let iterator = unwrap(
+        &env,
+        env.call_method(*list, "iterator", "()Ljava/util/Iterator;", &[]),
+    );
+    let iterator = unwrap(&env, env.get_iterator(unwrap(&env, iterator.l())));
+    assert!(unwrap(&env, iterator.has_next()));
+    let first = unwrap(&env, iterator.next_elem()).unwrap();
+    let first: String = unwrap(&env, env.get_string(first.into())).into();
+    assert_eq!(first, "a");
+
+    // An iterator is iterable too, from where it is.
+    let iterable = unwrap(&env, env.get_iterable(*iterator));
+    assert_eq!(collect_strings(&env, &iterable), ["b", "c"]);
+    assert!(unwrap(&env, iterator.next_elem()).is_none());
+}
+
+#[test]
+pub fn iterable_requires_iterator_method() {
+    let env = attach_current_thread();
+    let s = unwrap(&env, env.new_string("not iterable"));
+
+    assert!(env.get_iterable(s.into()).is_err());
+    unwrap(&env, env.exception_clear());
+}
+
+#[test]
+pub fn iterate_long_collection() {
+    let env = attach_current_thread();
+    let list = unwrap(&env, JList::new_array_list(&env, 0));
+
+    // Longer than the local frame of the attached thread: the local reference
+    // to each element is deleted when it is dropped.
+    const LEN: usize = 10_000;
+    for i in 0..LEN {
+        let s = env.auto_local(unwrap(&env, env.new_string(i.to_string())));
+        unwrap(&env, list.add_all(Some(&s)));
+    }
+
+    let iterable = unwrap(&env, env.get_iterable(*list));
+    for _ in 0..2 {
+        let strings = collect_strings(&env, &iterable);
+        assert_eq!(strings.len(), LEN);
+        assert_eq!(strings[LEN - 1], (LEN - 1).to_string());
+    }
+}
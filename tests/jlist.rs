@@ -0,0 +1,172 @@
+#![cfg(feature = "invocation")]
+
+use jni::{
+    errors::{Error, Result},
+    objects::{AutoLocal, GlobalRef, JList, JObject},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn to_strings<'a>(env: &JNIEnv<'a>, objects: impl IntoIterator<Item = JObject<'a>>) -> Vec<String> {
+    objects
+        .into_iter()
+        .map(|obj| unwrap(env, env.get_string(obj.into())).into())
+        .collect()
+}
+
+fn elems_to_strings<'a: 'b, 'b>(
+    env: &JNIEnv<'a>,
+    elems: impl IntoIterator<Item = Result<AutoLocal<'a, 'b>>>,
+) -> Vec<String> {
+    elems
+        .into_iter()
+        .map(|elem| {
+            let elem = unwrap(env, elem);
+            unwrap(env, env.get_string(elem.as_obj().into())).into()
+        })
+        .collect()
+}
+
+fn new_list<'a: 'b, 'b>(env: &'b JNIEnv<'a>, data: &[&str]) -> JList<'a, 'b> {
+    let strings: Vec<JObject> = data
+        .iter()
+        .map(|s| unwrap(env, env.new_string(s)).into())
+        .collect();
+    unwrap(env, JList::from_objects(env, strings))
+}
+
+#[test]
+pub fn jlist_iterate_both_ways() {
+    let env = attach_current_thread();
+    let data = ["hello", "world", "from", "test"];
+    let list = new_list(&env, &data);
+
+    let iter = unwrap(&env, list.iter());
+    assert_eq!(iter.len(), 4);
+    assert_eq!(elems_to_strings(&env, iter), data);
+
+    let reversed = elems_to_strings(&env, unwrap(&env, list.iter()).rev());
+    assert_eq!(reversed, ["test", "from", "world", "hello"]);
+
+    let mut iter = unwrap(&env, list.iter());
+    let first = iter.next().unwrap();
+    let last = iter.next_back().unwrap();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(elems_to_strings(&env, vec![first, last]), ["hello", "test"]);
+    assert_eq!(elems_to_strings(&env, iter), ["world", "from"]);
+}
+
+#[test]
+pub fn jlist_set_search_and_clear() {
+    let env = attach_current_thread();
+    let list = new_list(&env, &["a", "b", "a", "c"]);
+
+    let a = unwrap(&env, env.new_string("a")).into();
+    let d = unwrap(&env, env.new_string("d")).into();
+    assert!(unwrap(&env, list.contains(a)));
+    assert!(!unwrap(&env, list.contains(d)));
+    assert_eq!(unwrap(&env, list.index_of(a)), Some(0));
+    assert_eq!(unwrap(&env, list.last_index_of(a)), Some(2));
+    assert_eq!(unwrap(&env, list.index_of(d)), None);
+
+    let previous = unwrap(&env, list.set(1, d));
+    assert_eq!(to_strings(&env, vec![previous]), ["b"]);
+    assert_eq!(
+        to_strings(&env, unwrap(&env, list.to_vec())),
+        ["a", "d", "a", "c"]
+    );
+
+    assert!(list.set(4, d).is_err());
+    unwrap(&env, env.exception_clear());
+
+    assert!(!unwrap(&env, list.is_empty()));
+    unwrap(&env, list.clear());
+    assert!(unwrap(&env, list.is_empty()));
+    assert!(unwrap(&env, list.iter()).next().is_none());
+}
+
+#[test]
+pub fn jlist_iter_yields_errors() {
+    let env = attach_current_thread();
+    let list = new_list(&env, &["a", "b"]);
+
+    let mut iter = unwrap(&env, list.iter());
+    unwrap(&env, list.clear());
+
+    // The iterator keeps the size the list had, and yields the errors of
+    // the elements that are gone.
+    assert_eq!(iter.len(), 2);
+    assert!(matches!(iter.next(), Some(Err(Error::JavaException))));
+    assert!(unwrap(&env, env.exception_check()));
+    unwrap(&env, env.exception_clear());
+    assert_eq!(iter.len(), 1);
+}
+
+#[test]
+pub fn jlist_sub_list() {
+    let env = attach_current_thread();
+    let list = new_list(&env, &["a", "b", "c", "d"]);
+
+    let sub_list = unwrap(&env, list.sub_list(1, 3));
+    assert_eq!(
+        elems_to_strings(&env, unwrap(&env, sub_list.iter())),
+        ["b", "c"]
+    );
+
+    // Changes to the sub-list are reflected in the list.
+    unwrap(&env, sub_list.clear());
+    assert_eq!(
+        elems_to_strings(&env, unwrap(&env, list.iter())),
+        ["a", "d"]
+    );
+}
+
+#[test]
+pub fn jlist_global_refs() {
+    let env = attach_current_thread();
+    let data = ["x", "y", "z"];
+    let list = new_list(&env, &data);
+
+    let globals: Vec<GlobalRef> = unwrap(&env, list.to_global_vec());
+    assert_eq!(
+        to_strings(&env, globals.iter().map(GlobalRef::as_obj)),
+        data
+    );
+
+    let copy = unwrap(&env, JList::from_objects(&env, &globals));
+    assert_eq!(unwrap(&env, copy.size()), 3);
+    unwrap(&env, copy.add_all(&globals));
+    assert_eq!(
+        elems_to_strings(&env, unwrap(&env, copy.iter())),
+        ["x", "y", "z", "x", "y", "z"]
+    );
+}
+
+#[test]
+pub fn jlist_long_list() {
+    let env = attach_current_thread();
+    let list = unwrap(&env, JList::new_array_list(&env, 0));
+
+    // Longer than the local frame of the attached thread, deleting the local
+    // references while adding and iterating.
+    const LEN: i32 = 10_000;
+    for i in 0..LEN {
+        let s = env.auto_local(unwrap(&env, env.new_string(i.to_string())));
+        unwrap(&env, list.add_all(Some(&s)));
+    }
+    assert_eq!(unwrap(&env, list.size()), LEN);
+
+    let mut count = 0;
+    for elem in unwrap(&env, list.iter()).rev() {
+        let elem = unwrap(&env, elem);
+        let s: String = unwrap(&env, env.get_string(elem.as_obj().into())).into();
+        assert_eq!(s, (LEN - 1 - count).to_string());
+        count += 1;
+    }
+    assert_eq!(count, LEN);
+
+    let globals = unwrap(&env, list.to_global_vec());
+    assert_eq!(globals.len(), LEN as usize);
+}
@@ -39,3 +39,51 @@ pub fn jmap_push_and_iterate() {
     let orig = data.to_vec();
     assert_eq!(orig, collected);
 }
+
+#[test]
+pub fn jmap_views_and_bulk_operations() {
+    let env = attach_current_thread();
+    let map_object = unwrap(&env, env.new_object("java/util/LinkedHashMap", "()V", &[]));
+    let map = unwrap(&env, env.get_map(map_object));
+    let to_string = |obj: JObject| -> String { unwrap(&env, env.get_string(obj.into())).into() };
+
+    let (one, two, three) = (
+        unwrap(&env, env.new_string("one")).into(),
+        unwrap(&env, env.new_string("two")).into(),
+        unwrap(&env, env.new_string("three")).into(),
+    );
+    assert!(unwrap(&env, map.is_empty()));
+    unwrap(&env, map.put(one, two));
+    unwrap(&env, map.put(two, three));
+    assert_eq!(unwrap(&env, map.size()), 2);
+    assert!(unwrap(&env, map.contains_key(one)));
+    assert!(!unwrap(&env, map.contains_key(three)));
+
+    let keys: Vec<String> = unwrap(&env, map.iter_keys())
+        .map(|key| to_string(key.as_obj()))
+        .collect();
+    assert_eq!(keys, ["one", "two"]);
+    let values: Vec<String> = unwrap(&env, map.iter_values())
+        .map(|value| to_string(value.as_obj()))
+        .collect();
+    assert_eq!(values, ["two", "three"]);
+
+    // The views are backed by the map.
+    let key_set = unwrap(&env, map.key_set());
+    assert!(unwrap(&env, key_set.remove(one)));
+    assert!(!unwrap(&env, map.contains_key(one)));
+    let values = unwrap(&env, map.values());
+    assert!(unwrap(&env, values.contains(three)));
+    assert_eq!(unwrap(&env, values.size()), 1);
+
+    let copy_object = unwrap(&env, env.new_object("java/util/HashMap", "()V", &[]));
+    let copy = unwrap(&env, env.get_map(copy_object));
+    unwrap(&env, copy.put_all(*map));
+    assert_eq!(unwrap(&env, copy.size()), 1);
+    assert_eq!(to_string(unwrap(&env, copy.get(two)).unwrap()), "three");
+
+    unwrap(&env, map.clear());
+    assert!(unwrap(&env, map.is_empty()));
+    assert!(unwrap(&env, key_set.is_empty()));
+    assert_eq!(unwrap(&env, copy.size()), 1);
+}
@@ -1,17 +1,21 @@
 #![cfg(feature = "invocation")]
 
-use std::str::FromStr;
+use std::{fmt::Debug, str::FromStr};
 
 use jni::{
     descriptors::Desc,
-    errors::Error,
+    errors::{Error, Exception, JniError},
     objects::{
-        AutoArray, AutoLocal, JByteBuffer, JList, JObject, JString, JThrowable, JValue, ReleaseMode,
+        ArrayElement, AutoArray, AutoLocal, CriticalArray, CriticalString, JByteBuffer, JList,
+        JLongArray, JObject, JObjectRefType, JPrimitiveArray, JString, JThrowable, JValue,
+        ReleaseMode,
     },
-    signature::JavaType,
+    signature::{JavaType, Primitive, ReturnType},
     strings::JNIString,
-    sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort, jsize},
-    JNIEnv,
+    sys::{
+        jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jobject, jshort, jsize, JNI_FALSE,
+    },
+    JNIEnv, JNIVersion,
 };
 
 mod util;
@@ -171,6 +175,137 @@ pub fn get_static_public_field_by_id() {
     assert_eq!(min_int_value, i32::min_value());
 }
 
+#[test]
+pub fn reflected_static_method_roundtrip() {
+    let env = attach_current_thread();
+
+    let method_id = unwrap(
+        &env,
+        env.get_static_method_id(MATH_CLASS, MATH_ABS_METHOD_NAME, MATH_ABS_SIGNATURE),
+    );
+    let method = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_static_method(MATH_CLASS, method_id),
+    ));
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(&method, "java/lang/reflect/Method")
+    ));
+    let name = unwrap(
+        &env,
+        env.call_method(&method, "getName", "()Ljava/lang/String;", &[]),
+    );
+    let name: String = unwrap(&env, env.get_string(unwrap(&env, name.l()).into())).into();
+    assert_eq!(name, MATH_ABS_METHOD_NAME);
+
+    let method_id = unwrap(&env, env.from_reflected_static_method(&method));
+    let val = unwrap(
+        &env,
+        env.call_static_method_unchecked(
+            MATH_CLASS,
+            method_id,
+            ReturnType::Primitive(Primitive::Int),
+            &[JValue::from(-10).to_jni()],
+        ),
+    );
+    assert_eq!(unwrap(&env, val.i()), 10);
+}
+
+#[test]
+pub fn reflected_method_roundtrip() {
+    let env = attach_current_thread();
+
+    let method = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_method(STRING_CLASS, (STRING_CLASS, "length", "()I")),
+    ));
+    let method_id = unwrap(&env, env.from_reflected_method(&method));
+
+    let s = unwrap(&env, env.new_string(TESTING_OBJECT_STR));
+    let val = unwrap(
+        &env,
+        env.call_method_unchecked(s, method_id, ReturnType::Primitive(Primitive::Int), &[]),
+    );
+    assert_eq!(unwrap(&env, val.i()), TESTING_OBJECT_STR.len() as jint);
+}
+
+#[test]
+pub fn reflected_static_field_roundtrip() {
+    let env = attach_current_thread();
+
+    let field = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_static_field(INTEGER_CLASS, (INTEGER_CLASS, "MIN_VALUE", "I")),
+    ));
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(&field, "java/lang/reflect/Field")
+    ));
+
+    let field_id = unwrap(&env, env.from_reflected_static_field(&field));
+    let min_int_value = unwrap(
+        &env,
+        env.get_static_field_unchecked(INTEGER_CLASS, field_id, JavaType::from_str("I").unwrap()),
+    );
+    assert_eq!(unwrap(&env, min_int_value.i()), i32::MIN);
+}
+
+#[test]
+pub fn reflected_field_roundtrip() {
+    let env = attach_current_thread();
+
+    let field = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_field(STRING_CLASS, (STRING_CLASS, "hash", "I")),
+    ));
+    let name = unwrap(
+        &env,
+        env.call_method(&field, "getName", "()Ljava/lang/String;", &[]),
+    );
+    let name: String = unwrap(&env, env.get_string(unwrap(&env, name.l()).into())).into();
+    assert_eq!(name, "hash");
+
+    let field_id = unwrap(&env, env.from_reflected_field(&field));
+    let s = unwrap(&env, env.new_string(TESTING_OBJECT_STR));
+    unwrap(&env, env.call_method(s, "hashCode", "()I", &[]));
+    let hash = unwrap(
+        &env,
+        env.get_field_unchecked(s, field_id, ReturnType::Primitive(Primitive::Int)),
+    );
+    assert_ne!(unwrap(&env, hash.i()), 0);
+}
+
+#[test]
+pub fn from_reflected_checks_static() {
+    let env = attach_current_thread();
+
+    let method = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_method(STRING_CLASS, (STRING_CLASS, "length", "()I")),
+    ));
+    match env.from_reflected_static_method(&method) {
+        Err(Error::WrongObjectType(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let field = env.auto_local(unwrap(
+        &env,
+        env.to_reflected_static_field(INTEGER_CLASS, (INTEGER_CLASS, "MIN_VALUE", "I")),
+    ));
+    assert!(matches!(
+        env.from_reflected_field(&field),
+        Err(Error::WrongObjectType(_))
+    ));
+    assert!(!unwrap(&env, env.exception_check()));
+}
+
+#[test]
+pub fn from_reflected_method_null_arg() {
+    let env = attach_current_thread();
+    let result = env.from_reflected_method(JObject::null());
+    assert!(matches!(result, Err(Error::NullPtr(_))));
+}
+
 #[test]
 pub fn pop_local_frame_pending_exception() {
     let env = attach_current_thread();
@@ -298,6 +433,52 @@ pub fn call_static_method_wrong_arg() {
     assert_pending_java_exception(&env);
 }
 
+#[test]
+pub fn call_nonvirtual_method_ok() {
+    let env = attach_current_thread();
+
+    let list = AutoLocal::new(
+        &env,
+        unwrap(&env, env.new_object(ARRAYLIST_CLASS, "()V", &[])),
+    );
+
+    // `ArrayList#toString` would return "[]", while `Object#toString` returns the class name
+    // and the identity hash code.
+    let value = unwrap(
+        &env,
+        env.call_nonvirtual_method(
+            &list,
+            "java/lang/Object",
+            "toString",
+            "()Ljava/lang/String;",
+            &[],
+        ),
+    );
+    let value: String = unwrap(&env, env.get_string(unwrap(&env, value.l()).into())).into();
+
+    assert!(value.starts_with("java.util.ArrayList@"), "{}", value);
+}
+
+#[test]
+pub fn call_nonvirtual_method_wrong_arg_count() {
+    let env = attach_current_thread();
+
+    let list = AutoLocal::new(
+        &env,
+        unwrap(&env, env.new_object(ARRAYLIST_CLASS, "()V", &[])),
+    );
+
+    let result = env.call_nonvirtual_method(
+        &list,
+        "java/lang/Object",
+        "toString",
+        "()Ljava/lang/String;",
+        &[JValue::from(1)],
+    );
+
+    assert!(matches!(result, Err(Error::InvalidArgList(_))));
+}
+
 #[test]
 pub fn java_byte_array_from_slice() {
     let env = attach_current_thread();
@@ -315,6 +496,38 @@ pub fn java_byte_array_from_slice() {
     assert_eq!(res[2], 3);
 }
 
+#[test]
+pub fn primitive_array_region_generic() {
+    let env = attach_current_thread();
+
+    let array: JPrimitiveArray<jlong> = unwrap(&env, env.new_primitive_array(4));
+    assert_eq!(unwrap(&env, env.get_array_length(array)), 4);
+
+    unwrap(&env, env.set_array_region(array, 1, &[i64::MIN, i64::MAX]));
+    let mut res = [1; 4];
+    unwrap(&env, env.get_array_region(array, 0, &mut res));
+    assert_eq!(res, [0, i64::MIN, i64::MAX, 0]);
+
+    // Arrays can be converted from and to objects, e.g. for method calls.
+    let copy = unwrap(
+        &env,
+        env.call_static_method(
+            "java/util/Arrays",
+            "copyOf",
+            "([JI)[J",
+            &[JValue::from(*array), JValue::from(2)],
+        ),
+    );
+    let copy: JLongArray = unwrap(&env, copy.l()).into();
+    let mut res = [1; 2];
+    unwrap(&env, env.get_long_array_region(copy, 0, &mut res));
+    assert_eq!(res, [0, i64::MIN]);
+
+    let result = env.get_array_region(array, 3, &mut res);
+    assert_exception(&result, "JNIEnv#get_array_region should throw exception");
+    assert_pending_java_exception(&env);
+}
+
 macro_rules! test_get_array_elements {
     ( $jni_get:tt, $jni_type:ty, $new_array:tt, $get_array:tt, $set_array:tt ) => {
         #[test]
@@ -339,6 +552,9 @@ macro_rules! test_get_array_elements {
                 // Check array size
                 assert_eq!(auto_ptr.size().unwrap(), 2);
 
+                // Check slice access
+                assert_eq!(*auto_ptr, *buf);
+
                 // Check pointer access
                 let ptr = auto_ptr.as_ptr();
                 assert_eq!(unsafe { *ptr.offset(0) } as i32, 0);
@@ -447,6 +663,25 @@ test_get_array_elements!(
     set_double_array_region
 );
 
+#[test]
+pub fn get_array_elements_as_mut_slice() {
+    let env = attach_current_thread();
+    let java_array = unwrap(&env, env.new_int_array(3));
+
+    {
+        let mut elements = unwrap(
+            &env,
+            env.get_array_elements(java_array, ReleaseMode::CopyBack),
+        );
+        unsafe { elements.as_mut_slice() }.copy_from_slice(&[1, 2, 3]);
+        assert_eq!(*elements, [1, 2, 3]);
+    }
+
+    let mut res = [0; 3];
+    unwrap(&env, env.get_int_array_region(java_array, 0, &mut res));
+    assert_eq!(res, [1, 2, 3]);
+}
+
 #[test]
 #[ignore] // Disabled until issue #283 is resolved
 pub fn get_long_array_elements_commit() {
@@ -511,42 +746,79 @@ pub fn get_primitive_array_critical() {
     // Use a scope to test Drop
     {
         // Get primitive array elements auto wrapper
-        let auto_ptr = env
-            .get_primitive_array_critical(java_array, ReleaseMode::CopyBack)
-            .unwrap();
+        let mut auto_ptr = unsafe {
+            env.get_primitive_array_critical(java_array, ReleaseMode::CopyBack)
+                .unwrap()
+        };
 
         // Check array size
         assert_eq!(auto_ptr.size().unwrap(), 3);
 
-        // Get pointer
-        let ptr = auto_ptr.as_ptr();
-
-        // Convert void pointer to an unsigned byte array, without copy
-        let mut vec;
-        unsafe { vec = Vec::from_raw_parts(ptr as *mut u8, 3, 3) }
-
         // Check
-        assert_eq!(vec[0], 1);
-        assert_eq!(vec[1], 2);
-        assert_eq!(vec[2], 3);
+        assert_eq!(*auto_ptr, [1, 2, 3]);
 
         // Modify
-        vec[0] += 1;
-        vec[1] += 1;
-        vec[2] += 1;
-
-        // Release
-        // Make sure vec's destructor doesn't free the data it thinks it owns when it goes out
-        // of scope (avoid double free)
-        std::mem::forget(vec);
+        for elem in auto_ptr.iter_mut() {
+            *elem += 1;
+        }
     }
 
     // Confirm modification of original Java array
     let mut res: [i8; 3] = [0; 3];
     env.get_byte_array_region(java_array, 0, &mut res).unwrap();
-    assert_eq!(res[0], 2);
-    assert_eq!(res[1], 3);
-    assert_eq!(res[2], 4);
+    assert_eq!(res, [2, 3, 4]);
+}
+
+#[test]
+pub fn get_primitive_array_critical_split_env() {
+    let env = attach_current_thread();
+
+    let source = unwrap(&env, env.new_int_array(3));
+    unwrap(&env, env.set_int_array_region(source, 0, &[1, 2, 3]));
+    let target = unwrap(&env, env.new_int_array(3));
+    let s = unwrap(&env, env.new_string("abc"));
+    // The lengths are queried before entering the critical region.
+    let critical_target = unwrap(&env, CriticalArray::new(&env, target));
+    let critical_s = unwrap(&env, CriticalString::new(&env, s));
+
+    {
+        let mut source = unsafe {
+            env.get_primitive_array_critical(source, ReleaseMode::NoCopyBack)
+                .unwrap()
+        };
+        let (source, critical_env) = source.split_env();
+        let mut target = unsafe {
+            critical_env
+                .get_primitive_array_critical(critical_target, ReleaseMode::CopyBack)
+                .unwrap()
+        };
+        target.copy_from_slice(source);
+
+        let chars = critical_env.get_string_utf16_critical(critical_s).unwrap();
+        assert_eq!(chars.len(), 3);
+    }
+
+    let mut res = [0; 3];
+    unwrap(&env, env.get_int_array_region(target, 0, &mut res));
+    assert_eq!(res, [1, 2, 3]);
+
+    // The other way around, from a critical string.
+    let chars = unwrap(&env, env.new_char_array(3));
+    let critical_chars = unwrap(&env, CriticalArray::new(&env, chars));
+    {
+        let s = unsafe { env.get_string_utf16_critical(s).unwrap() };
+        let (s, critical_env) = s.split_env();
+        let mut chars = unsafe {
+            critical_env
+                .get_primitive_array_critical(critical_chars, ReleaseMode::CopyBack)
+                .unwrap()
+        };
+        chars.copy_from_slice(s);
+    }
+
+    let mut res = [0; 3];
+    unwrap(&env, env.get_char_array_region(chars, 0, &mut res));
+    assert_eq!(res, [b'a' as u16, b'b' as u16, b'c' as u16]);
 }
 
 #[test]
@@ -720,6 +992,32 @@ fn get_super_class_null() {
     assert!(result.unwrap().is_null());
 }
 
+#[test]
+fn get_module() {
+    let env = attach_current_thread();
+    let result = env.get_module(STRING_CLASS);
+
+    if unwrap(&env, env.get_version()) < JNIVersion::V9 {
+        assert!(matches!(
+            result,
+            Err(Error::JniCall(JniError::WrongVersion))
+        ));
+        return;
+    }
+
+    let module = env.auto_local(unwrap(&env, result));
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(&module, "java/lang/Module")
+    ));
+    let name = unwrap(
+        &env,
+        env.call_method(&module, "getName", "()Ljava/lang/String;", &[]),
+    );
+    let name: String = unwrap(&env, env.get_string(unwrap(&env, name.l()).into())).into();
+    assert_eq!(name, "java.base");
+}
+
 #[test]
 fn convert_byte_array() {
     let env = attach_current_thread();
@@ -731,6 +1029,90 @@ fn convert_byte_array() {
     assert_eq!(dest.unwrap(), src);
 }
 
+fn assert_array_roundtrip<T: ArrayElement + PartialEq + Debug>(env: &JNIEnv, src: &[T]) {
+    let array = unwrap(env, env.array_from_slice(src));
+    assert_eq!(unwrap(env, env.get_array_length(array)), src.len() as jsize);
+    let dest: Vec<T> = unwrap(env, env.convert_array(array));
+    assert_eq!(dest, src);
+}
+
+#[test]
+fn convert_primitive_arrays() {
+    let env = attach_current_thread();
+
+    assert_array_roundtrip::<jint>(&env, &[jint::MIN, 0, jint::MAX]);
+    assert_array_roundtrip::<jlong>(&env, &[jlong::MIN, 0, jlong::MAX]);
+    assert_array_roundtrip::<jdouble>(&env, &[-1.5, 0.0, f64::MAX]);
+    assert_array_roundtrip::<jfloat>(&env, &[-1.5, 0.0, f32::MAX]);
+    assert_array_roundtrip::<jshort>(&env, &[jshort::MIN, 0, jshort::MAX]);
+    assert_array_roundtrip::<jchar>(&env, &[0, 0xe9, jchar::MAX]);
+    assert_array_roundtrip::<jbyte>(&env, &[jbyte::MIN, 0, jbyte::MAX]);
+    assert_array_roundtrip::<u8>(&env, &[0, 0x80, u8::MAX]);
+    assert_array_roundtrip(&env, &[true, false, true]);
+    assert_array_roundtrip::<jint>(&env, &[]);
+
+    // Booleans are stored as `boolean[]`.
+    let array = unwrap(&env, env.array_from_slice(&[false, true]));
+    let mut res = [2; 2];
+    unwrap(&env, env.get_boolean_array_region(array, 0, &mut res));
+    assert_eq!(res, [0, 1]);
+}
+
+#[test]
+fn new_string_utf16_roundtrip() {
+    let env = attach_current_thread();
+    let src = "h\u{e9}llo \u{1F600}";
+    let utf16: Vec<jchar> = src.encode_utf16().collect();
+
+    let s = unwrap(&env, env.new_string_utf16(&utf16));
+    let back: String = unwrap(&env, env.get_string(s)).into();
+    assert_eq!(back, src);
+
+    assert_eq!(unwrap(&env, env.get_string_length(s)), utf16.len() as jsize);
+    // Modified UTF-8 encodes the supplementary character as a surrogate pair
+    // of 3 bytes each.
+    assert_eq!(unwrap(&env, env.get_string_utf_length(s)), 13);
+}
+
+#[test]
+fn get_string_utf16() {
+    let env = attach_current_thread();
+    let src = "h\u{e9}llo \u{1F600}";
+    let s = unwrap(&env, env.new_string(src));
+    let expected: Vec<jchar> = src.encode_utf16().collect();
+
+    {
+        let chars = unwrap(&env, env.get_string_utf16(s));
+        assert_eq!(&*chars, &expected[..]);
+        assert_eq!(String::from(chars), src);
+    }
+
+    let critical_copy = {
+        let chars = unsafe { env.get_string_utf16_critical(s).unwrap() };
+        chars.to_vec()
+    };
+    assert_eq!(critical_copy, expected);
+}
+
+#[test]
+fn get_string_region() {
+    let env = attach_current_thread();
+    let s = unwrap(&env, env.new_string("abcdef"));
+
+    let mut buf = [0 as jchar; 3];
+    unwrap(&env, env.get_string_region(s, 2, &mut buf));
+    let expected: Vec<jchar> = "cde".encode_utf16().collect();
+    assert_eq!(&buf[..], &expected[..]);
+
+    let result = env.get_string_region(s, 5, &mut buf);
+    assert!(result.is_err());
+    assert_pending_java_exception_detailed(
+        &env,
+        Some("java/lang/StringIndexOutOfBoundsException"),
+        None,
+    );
+}
+
 #[test]
 fn local_ref_null() {
     let env = attach_current_thread();
@@ -745,6 +1127,41 @@ fn local_ref_null() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn get_object_ref_type() {
+    let env = attach_current_thread();
+    let local = env.auto_local(unwrap(&env, env.new_string(TESTING_OBJECT_STR)));
+    let global = unwrap(&env, env.new_global_ref(&local));
+    let weak = unwrap(&env, env.new_weak_ref(&local)).expect("weak ref should not be null");
+
+    assert_eq!(
+        unwrap(&env, env.get_object_ref_type(&local)),
+        JObjectRefType::Local
+    );
+    assert_eq!(
+        unwrap(&env, env.get_object_ref_type(global.as_obj())),
+        JObjectRefType::Global
+    );
+    assert_eq!(
+        unwrap(&env, env.get_object_ref_type(JObject::from(weak.as_raw()))),
+        JObjectRefType::WeakGlobal
+    );
+    assert_eq!(
+        unwrap(&env, env.get_object_ref_type(JObject::null())),
+        JObjectRefType::Invalid
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "delete_local_ref: expected a Local reference but got Global")]
+fn delete_local_ref_of_global_ref() {
+    let env = attach_current_thread();
+    let local = env.auto_local(unwrap(&env, env.new_string(TESTING_OBJECT_STR)));
+    let global = unwrap(&env, env.new_global_ref(&local));
+    let _ = env.delete_local_ref(global.as_obj());
+}
+
 #[test]
 fn new_global_ref_null() {
     let env = attach_current_thread();
@@ -798,7 +1215,7 @@ fn short_lifetime_list_sub_fn_get_first_element<'a>(
     list: &'_ JList<'a, '_>,
 ) -> Result<JObject<'a>, Error> {
     let mut iterator = list.iter()?;
-    Ok(iterator.next().unwrap())
+    Ok(iterator.next().unwrap()?.forget())
 }
 
 #[test]
@@ -846,6 +1263,88 @@ pub fn throw_defaults() {
     test_throwable_descriptor_with_default_type(&env, JNIString::from(TEST_EXCEPTION_MESSAGE));
 }
 
+#[test]
+pub fn catch_and_throw_ok() {
+    let env = attach_current_thread();
+
+    let result: jint = env.catch_and_throw(|| {
+        let x = JValue::from(-10);
+        env.call_static_method(MATH_CLASS, MATH_ABS_METHOD_NAME, MATH_ABS_SIGNATURE, &[x])?
+            .i()
+    });
+
+    assert_eq!(result, 10);
+    assert!(!unwrap(&env, env.exception_check()));
+}
+
+#[test]
+pub fn catch_and_throw_error() {
+    let env = attach_current_thread();
+
+    let result: jobject = env.catch_and_throw(|| env.get_object_class(JObject::null()));
+
+    assert!(result.is_null());
+    assert_pending_java_exception_detailed(
+        &env,
+        Some("java/lang/NullPointerException"),
+        Some("Null pointer in get_object_class"),
+    );
+}
+
+#[test]
+pub fn catch_and_throw_keeps_pending_exception() {
+    let env = attach_current_thread();
+
+    let result: jint = env.catch_and_throw(|| {
+        let x = JValue::Long(i64::MAX);
+        env.call_static_method(
+            MATH_CLASS,
+            MATH_TO_INT_METHOD_NAME,
+            MATH_TO_INT_SIGNATURE,
+            &[x],
+        )?
+        .i()
+    });
+
+    assert_eq!(result, 0);
+    assert_pending_java_exception_detailed(&env, Some(ARITHMETIC_EXCEPTION_CLASS), None);
+}
+
+#[test]
+pub fn catch_and_throw_custom_exception() {
+    let env = attach_current_thread();
+
+    let result: jboolean = env.catch_and_throw(|| -> Result<bool, Exception> {
+        Err(Exception {
+            class: ARITHMETIC_EXCEPTION_CLASS.to_string(),
+            msg: TEST_EXCEPTION_MESSAGE.to_string(),
+        })
+    });
+
+    assert_eq!(result, JNI_FALSE);
+    assert_pending_java_exception_detailed(
+        &env,
+        Some(ARITHMETIC_EXCEPTION_CLASS),
+        Some(TEST_EXCEPTION_MESSAGE),
+    );
+}
+
+#[test]
+pub fn catch_and_throw_panic() {
+    let env = attach_current_thread();
+
+    let result: jobject = env.catch_and_throw(|| -> JString {
+        panic!("{}", TEST_EXCEPTION_MESSAGE);
+    });
+
+    assert!(result.is_null());
+    assert_pending_java_exception_detailed(
+        &env,
+        Some(RUNTIME_EXCEPTION_CLASS),
+        Some(TEST_EXCEPTION_MESSAGE),
+    );
+}
+
 #[test]
 pub fn test_conversion() {
     let env = attach_current_thread();
@@ -877,7 +1376,7 @@ where
 }
 
 // Helper method that asserts that result is Error and the cause is JavaException.
-fn assert_exception(res: &Result<jobject, Error>, expect_message: &str) {
+fn assert_exception<T: std::fmt::Debug>(res: &Result<T, Error>, expect_message: &str) {
     assert!(res.is_err());
     assert!(res
         .as_ref()
@@ -0,0 +1,127 @@
+#![cfg(feature = "invocation")]
+// Exception capture is a process-wide setting, so these tests live in their
+// own test binary to not affect the exception handling of the other tests.
+
+use jni::{
+    errors::{self, Error, JavaExceptionDetails, Result},
+    objects::{JObject, JValue},
+    sys::jint,
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn to_int_exact_overflow(env: &JNIEnv) -> Result<jint> {
+    env.call_static_method(
+        "java/lang/Math",
+        "toIntExact",
+        "(J)I",
+        &[JValue::Long(i64::MAX)],
+    )?
+    .i()
+}
+
+fn expect_caught(result: Result<impl std::fmt::Debug>) -> Box<JavaExceptionDetails> {
+    match result {
+        Err(Error::CaughtJavaException(details)) => details,
+        other => panic!("expected a caught Java exception, got {:?}", other),
+    }
+}
+
+#[test]
+fn exception_details_are_captured() {
+    errors::set_exception_capture(true);
+    let env = attach_current_thread();
+
+    let details = expect_caught(to_int_exact_overflow(&env));
+    assert!(!unwrap(&env, env.exception_check()));
+
+    assert_eq!(details.class, "java.lang.ArithmeticException");
+    assert_eq!(details.message.as_deref(), Some("integer overflow"));
+    assert!(details.causes.is_empty());
+    assert!(details
+        .stack_trace
+        .starts_with("java.lang.ArithmeticException: integer overflow"));
+    assert!(details
+        .stack_trace
+        .contains("at java.base/java.lang.Math.toIntExact"));
+    assert_eq!(
+        details.to_string(),
+        "java.lang.ArithmeticException: integer overflow"
+    );
+
+    unwrap(&env, details.rethrow(&env));
+    assert!(unwrap(&env, env.exception_check()));
+    let exception = unwrap(&env, env.exception_occurred());
+    unwrap(&env, env.exception_clear());
+    assert!(unwrap(
+        &env,
+        env.is_same_object(exception, details.throwable().as_obj())
+    ));
+}
+
+#[test]
+fn exception_cause_chain_is_captured() {
+    errors::set_exception_capture(true);
+    let env = attach_current_thread();
+
+    // `Method#invoke` wraps exceptions in an `InvocationTargetException`.
+    let method = unwrap(
+        &env,
+        env.to_reflected_static_method(
+            "java/lang/Integer",
+            ("java/lang/Integer", "parseInt", "(Ljava/lang/String;)I"),
+        ),
+    );
+    let input = unwrap(&env, env.new_string("abc"));
+    let args = unwrap(&env, env.new_object_array(1, "java/lang/Object", input));
+
+    let result = env.call_method(
+        method,
+        "invoke",
+        "(Ljava/lang/Object;[Ljava/lang/Object;)Ljava/lang/Object;",
+        &[JValue::from(JObject::null()), JValue::from(args)],
+    );
+    let details = expect_caught(result);
+
+    assert_eq!(details.class, "java.lang.reflect.InvocationTargetException");
+    assert_eq!(details.message, None);
+    assert_eq!(details.causes.len(), 1);
+    assert_eq!(details.causes[0].class, "java.lang.NumberFormatException");
+    assert_eq!(
+        details.causes[0].message.as_deref(),
+        Some("For input string: \"abc\"")
+    );
+    assert!(details
+        .stack_trace
+        .contains("Caused by: java.lang.NumberFormatException"));
+    assert_eq!(
+        details.to_string(),
+        "java.lang.reflect.InvocationTargetException, \
+         caused by java.lang.NumberFormatException: For input string: \"abc\""
+    );
+}
+
+#[test]
+fn catch_and_throw_rethrows_captured_exception() {
+    errors::set_exception_capture(true);
+    let env = attach_current_thread();
+
+    let result: jint = env.catch_and_throw(|| to_int_exact_overflow(&env));
+    assert_eq!(result, 0);
+
+    assert!(unwrap(&env, env.exception_check()));
+    let exception = unwrap(&env, env.exception_occurred());
+    unwrap(&env, env.exception_clear());
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(exception, "java/lang/ArithmeticException")
+    ));
+    let message = unwrap(
+        &env,
+        env.call_method(exception, "getMessage", "()Ljava/lang/String;", &[]),
+    );
+    let message: String = unwrap(&env, env.get_string(unwrap(&env, message.l()).into())).into();
+    assert_eq!(message, "integer overflow");
+}
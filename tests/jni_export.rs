@@ -0,0 +1,178 @@
+#![cfg(all(feature = "invocation", feature = "macros"))]
+
+use jni::{
+    errors::Error,
+    jni_export,
+    objects::{JClass, JIntArray, JObject, JString},
+    sys::{jboolean, jint, JNI_FALSE, JNI_TRUE},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+#[jni_export(class = "com.example.HelloWorld")]
+fn hello<'a>(env: JNIEnv<'a>, _class: JClass<'a>, input: JString<'a>) -> JString<'a> {
+    let input: String = env.get_string(input).unwrap().into();
+    env.new_string(format!("Hello, {}!", input)).unwrap()
+}
+
+#[jni_export(class = "com/example/my_pkg/Outer$Inner", name = "add_one")]
+fn add_one(_env: JNIEnv, _this: JObject, value: jint, enabled: bool) -> jint {
+    if enabled {
+        value + 1
+    } else {
+        value
+    }
+}
+
+#[jni_export(class = "com.example.Flags", name = "invert")]
+fn invert(_env: JNIEnv, _class: JClass, flag: jboolean) -> jboolean {
+    (flag == JNI_FALSE).into()
+}
+
+#[jni_export(class = "com.example.Overloads", name = "sum", signature = "(II)I")]
+fn sum_ints(_env: JNIEnv, _class: JClass, a: jint, b: jint) -> jint {
+    a + b
+}
+
+#[jni_export(class = "com.example.Overloads", name = "sum", signature = "([I)I")]
+fn sum_array(env: JNIEnv, _class: JClass, array: JIntArray) -> jint {
+    let len = env.get_array_length(array).unwrap();
+    let mut buf = vec![0; len as usize];
+    env.get_int_array_region(array, 0, &mut buf).unwrap();
+    buf.iter().sum()
+}
+
+#[jni_export(class = "com.example.Errors", name = "objectClass")]
+fn object_class<'a>(
+    env: JNIEnv<'a>,
+    _class: JClass<'a>,
+    obj: JObject<'a>,
+) -> Result<JClass<'a>, Error> {
+    env.get_object_class(obj)
+}
+
+#[jni_export(class = "com.example.Panics")]
+fn panics<'a>(_env: JNIEnv<'a>, _class: JClass<'a>) -> JString<'a> {
+    panic!("something went wrong")
+}
+
+#[jni_export(class = "com.example.Panics", name = "nothing")]
+unsafe fn unsafe_nothing(_env: JNIEnv, _class: JClass) {}
+
+#[test]
+fn jni_export_converts_args_and_return() {
+    let env = attach_current_thread();
+
+    let input = unwrap(&env, env.new_string("World"));
+    let output =
+        Java_com_example_HelloWorld_hello(*env, JObject::null().into_inner(), input.into_inner());
+    assert!(!output.is_null());
+
+    let output: String = unwrap(&env, env.get_string(output.into())).into();
+    assert_eq!(output, "Hello, World!");
+}
+
+#[test]
+fn jni_export_mangles_names() {
+    let env = attach_current_thread();
+
+    assert_eq!(
+        Java_com_example_my_1pkg_Outer_00024Inner_add_1one(
+            *env,
+            JObject::null().into_inner(),
+            41,
+            JNI_TRUE
+        ),
+        42
+    );
+    assert_eq!(
+        Java_com_example_Overloads_sum__II(*env, JObject::null().into_inner(), 1, 2),
+        3
+    );
+
+    let array = unwrap(&env, env.new_int_array(3));
+    unwrap(&env, env.set_int_array_region(array, 0, &[1, 2, 3]));
+    assert_eq!(
+        Java_com_example_Overloads_sum___3I(*env, JObject::null().into_inner(), array.into_inner()),
+        6
+    );
+
+    Java_com_example_Panics_nothing(*env, JObject::null().into_inner());
+}
+
+#[test]
+fn jni_export_converts_booleans() {
+    let env = attach_current_thread();
+
+    assert_eq!(
+        Java_com_example_Flags_invert(*env, JObject::null().into_inner(), JNI_TRUE),
+        JNI_FALSE
+    );
+    assert_eq!(
+        Java_com_example_Flags_invert(*env, JObject::null().into_inner(), JNI_FALSE),
+        JNI_TRUE
+    );
+    // Any non-zero byte is true.
+    assert_eq!(
+        Java_com_example_my_1pkg_Outer_00024Inner_add_1one(
+            *env,
+            JObject::null().into_inner(),
+            1,
+            2
+        ),
+        2
+    );
+}
+
+#[test]
+fn jni_export_throws_errors() {
+    let env = attach_current_thread();
+
+    let s = unwrap(&env, env.new_string("test"));
+    let class =
+        Java_com_example_Errors_objectClass(*env, JObject::null().into_inner(), s.into_inner());
+    assert!(unwrap(
+        &env,
+        env.is_assignable_from(JClass::from(class), "java/lang/String")
+    ));
+
+    let class = Java_com_example_Errors_objectClass(
+        *env,
+        JObject::null().into_inner(),
+        JObject::null().into_inner(),
+    );
+    assert!(class.is_null());
+
+    assert!(unwrap(&env, env.exception_check()));
+    let exception = unwrap(&env, env.exception_occurred());
+    unwrap(&env, env.exception_clear());
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(exception, "java/lang/NullPointerException")
+    ));
+}
+
+#[test]
+fn jni_export_catches_panics() {
+    let env = attach_current_thread();
+
+    let output = Java_com_example_Panics_panics(*env, JObject::null().into_inner());
+    assert!(output.is_null());
+
+    assert!(unwrap(&env, env.exception_check()));
+    let exception = unwrap(&env, env.exception_occurred());
+    unwrap(&env, env.exception_clear());
+    assert!(unwrap(
+        &env,
+        env.is_instance_of(exception, "java/lang/RuntimeException")
+    ));
+
+    let message = unwrap(
+        &env,
+        env.call_method(exception, "getMessage", "()Ljava/lang/String;", &[]),
+    );
+    let message: String = unwrap(&env, env.get_string(unwrap(&env, message.l()).into())).into();
+    assert_eq!(message, "something went wrong");
+}
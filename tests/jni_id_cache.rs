@@ -0,0 +1,84 @@
+#![cfg(feature = "invocation")]
+// The ID cache is a process-wide setting, so these tests live in their own
+// test binary to not affect the other tests.
+
+use jni::{
+    descriptors::{self, Desc},
+    objects::{JMethodID, JObject, JValue},
+    JNIEnv,
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+fn hash_code(env: &JNIEnv, obj: JObject) -> i32 {
+    unwrap(env, env.call_method(obj, "hashCode", "()I", &[]))
+        .i()
+        .unwrap()
+}
+
+fn size_field(env: &JNIEnv, class: &str) -> i32 {
+    unwrap(env, env.get_static_field(class, "SIZE", "I"))
+        .i()
+        .unwrap()
+}
+
+#[test]
+fn id_cache_is_keyed_by_class() {
+    descriptors::set_id_cache_enabled(true);
+    assert!(descriptors::is_id_cache_enabled());
+    let env = attach_current_thread();
+
+    let s = unwrap(&env, env.new_string("hello"));
+    let int = unwrap(
+        &env,
+        env.new_object("java/lang/Integer", "(I)V", &[JValue::Int(42)]),
+    );
+    // Repeated calls use the cached IDs, for the right class.
+    for _ in 0..2 {
+        assert_eq!(hash_code(&env, s.into()), 99162322); // "hello".hashCode()
+        assert_eq!(hash_code(&env, int), 42);
+
+        assert_eq!(size_field(&env, "java/lang/Integer"), 32);
+        assert_eq!(size_field(&env, "java/lang/Long"), 64);
+    }
+
+    let id1: JMethodID = unwrap(&env, ("java/lang/String", "length", "()I").lookup(&env));
+    let id2: JMethodID = unwrap(&env, ("java/lang/String", "length", "()I").lookup(&env));
+    assert_eq!(id1.into_inner(), id2.into_inner());
+}
+
+#[test]
+fn id_cache_invalidation() {
+    descriptors::set_id_cache_enabled(true);
+    let env = attach_current_thread();
+
+    let int = unwrap(
+        &env,
+        env.new_object("java/lang/Integer", "(I)V", &[JValue::Int(7)]),
+    );
+    assert_eq!(hash_code(&env, int), 7);
+
+    // `java.lang.Integer` is loaded by the bootstrap class loader.
+    unwrap(
+        &env,
+        descriptors::invalidate_class_loader(&env, JObject::null()),
+    );
+    assert_eq!(hash_code(&env, int), 7);
+
+    descriptors::clear_id_cache();
+    assert_eq!(hash_code(&env, int), 7);
+}
+
+#[test]
+fn id_cache_lookup_errors() {
+    descriptors::set_id_cache_enabled(true);
+    let env = attach_current_thread();
+
+    for _ in 0..2 {
+        let res: jni::errors::Result<JMethodID> =
+            ("java/lang/String", "noSuchMethod", "()V").lookup(&env);
+        assert!(res.is_err());
+        unwrap(&env, env.exception_clear());
+    }
+}
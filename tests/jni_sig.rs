@@ -0,0 +1,108 @@
+#![cfg(all(feature = "invocation", feature = "macros"))]
+
+use jni::{
+    jni_sig,
+    objects::{JObject, JString},
+    signature::MethodSignature,
+    sys::{jint, jlong},
+};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+const INDEX_OF: MethodSignature<(JString<'static>, jint), jint> =
+    jni_sig!("(Ljava/lang/String;I)I");
+
+#[test]
+fn jni_sig_as_str() {
+    assert_eq!(INDEX_OF.as_str(), "(Ljava/lang/String;I)I");
+    assert_eq!(jni_sig!("()V").to_string(), "()V");
+}
+
+#[test]
+fn call_method_typed() {
+    let env = attach_current_thread();
+
+    let s = unwrap(&env, env.new_string("hello"));
+    let needle = unwrap(&env, env.new_string("lo"));
+
+    let index = unwrap(
+        &env,
+        env.call_method_typed(s, "indexOf", INDEX_OF, (needle, 0)),
+    );
+    assert_eq!(index, 3);
+
+    let empty = unwrap(
+        &env,
+        env.call_method_typed(s, "isEmpty", jni_sig!("()Z"), ()),
+    );
+    assert!(!empty);
+
+    let sub: JString = unwrap(
+        &env,
+        env.call_method_typed(s, "substring", jni_sig!("(II)Ljava/lang/String;"), (1, 4)),
+    );
+    let sub: String = unwrap(&env, env.get_string(sub)).into();
+    assert_eq!(sub, "ell");
+}
+
+#[test]
+fn call_static_method_typed() {
+    let env = attach_current_thread();
+
+    let max = unwrap(
+        &env,
+        env.call_static_method_typed("java/lang/Math", "max", jni_sig!("(II)I"), (-1, 7)),
+    );
+    assert_eq!(max, 7);
+
+    let s = unwrap(&env, env.new_string("-42"));
+    let value: jlong = unwrap(
+        &env,
+        env.call_static_method_typed(
+            "java/lang/Long",
+            "parseLong",
+            jni_sig!("(Ljava/lang/String;)J"),
+            (s,),
+        ),
+    );
+    assert_eq!(value, -42);
+}
+
+#[test]
+fn new_object_typed() {
+    let env = attach_current_thread();
+
+    let initial = unwrap(&env, env.new_string("foo"));
+    let builder = unwrap(
+        &env,
+        env.new_object_typed(
+            "java/lang/StringBuilder",
+            jni_sig!("(Ljava/lang/String;)V"),
+            (initial,),
+        ),
+    );
+
+    let returned: JObject = unwrap(
+        &env,
+        env.call_method_typed(
+            builder,
+            "append",
+            jni_sig!("(C)Ljava/lang/StringBuilder;"),
+            ('!' as u16,),
+        ),
+    );
+    assert!(unwrap(&env, env.is_same_object(returned, builder)));
+
+    unwrap(
+        &env,
+        env.call_method_typed(builder, "setLength", jni_sig!("(I)V"), (3,)),
+    );
+
+    let s = unwrap(
+        &env,
+        env.call_method_typed(builder, "toString", jni_sig!("()Ljava/lang/String;"), ()),
+    );
+    let s: String = unwrap(&env, env.get_string(s)).into();
+    assert_eq!(s, "foo");
+}
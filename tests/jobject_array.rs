@@ -0,0 +1,85 @@
+#![cfg(feature = "invocation")]
+
+use jni::{errors::Error, objects::JObject};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+#[test]
+pub fn string_array_roundtrip() {
+    let env = attach_current_thread();
+    let data = ["hello", "world", "from", "test"];
+
+    let array = unwrap(&env, env.new_string_array(&data));
+    assert_eq!(unwrap(&env, env.get_array_length(array)), 4);
+    assert_eq!(unwrap(&env, env.get_string_array(array)), data);
+
+    let empty = unwrap(&env, env.new_string_array::<&str>(&[]));
+    assert!(unwrap(&env, env.get_string_array(empty)).is_empty());
+}
+
+#[test]
+pub fn iterate_object_array() {
+    let env = attach_current_thread();
+    let strings: Vec<JObject> = ["a", "b", "c"]
+        .iter()
+        .map(|s| unwrap(&env, env.new_string(s)).into())
+        .collect();
+    let globals: Vec<_> = strings
+        .iter()
+        .map(|s| unwrap(&env, env.new_global_ref(*s)))
+        .collect();
+
+    let array = unwrap(
+        &env,
+        env.object_array_from_iter("java/lang/String", &globals),
+    );
+    let iter = unwrap(&env, env.get_object_array_iter(array));
+    assert_eq!(iter.len(), 3);
+    let reversed: Vec<String> = iter
+        .rev()
+        .map(|s| unwrap(&env, env.get_string(unwrap(&env, s).as_obj().into())).into())
+        .collect();
+    assert_eq!(reversed, ["c", "b", "a"]);
+
+    let array = unwrap(
+        &env,
+        env.object_array_from_iter("java/lang/Object", strings),
+    );
+    assert_eq!(unwrap(&env, env.get_string_array(array)), ["a", "b", "c"]);
+}
+
+#[test]
+pub fn object_array_index_out_of_bounds() {
+    let env = attach_current_thread();
+    let array = unwrap(&env, env.new_string_array(&["a"]));
+
+    match env.get_object_array_element(array, 1) {
+        Err(Error::IndexOutOfBounds(1)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    match env.set_object_array_element(array, -1, JObject::null()) {
+        Err(Error::IndexOutOfBounds(-1)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(!unwrap(&env, env.exception_check()));
+
+    // Other exceptions are reported as usual.
+    let integer = unwrap(
+        &env,
+        env.new_object("java/lang/Integer", "(I)V", &[1.into()]),
+    );
+    match env.set_object_array_element(array, 0, integer) {
+        Err(Error::JavaException) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(unwrap(&env, env.exception_check()));
+    unwrap(&env, env.exception_clear());
+
+    // Null elements are rejected when reading strings.
+    unwrap(
+        &env,
+        env.set_object_array_element(array, 0, JObject::null()),
+    );
+    assert!(env.get_string_array(array).is_err());
+}
@@ -0,0 +1,187 @@
+#![cfg(all(feature = "invocation", feature = "serde"))]
+
+use std::collections::{BTreeMap, HashMap};
+
+use jni::{
+    errors::Error,
+    objects::JObject,
+    serde::{from_java, to_java},
+    JNIEnv,
+};
+use serde::{Deserialize, Serialize};
+
+mod util;
+use util::{attach_current_thread, unwrap};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    port: u16,
+    ratio: f64,
+    tags: Vec<String>,
+    limits: BTreeMap<String, i64>,
+    parent: Option<Box<Config>>,
+    mode: Mode,
+    #[serde(with = "serde_bytes")]
+    key: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    Off,
+    Fixed(u32),
+    Range { min: i8, max: i8 },
+    Pair(char, bool),
+}
+
+fn to_string(env: &JNIEnv, obj: JObject) -> String {
+    let s = unwrap(
+        env,
+        env.call_static_method(
+            "java/util/Objects",
+            "toString",
+            "(Ljava/lang/Object;)Ljava/lang/String;",
+            &[obj.into()],
+        ),
+    );
+    unwrap(env, env.get_string(unwrap(env, s.l()).into())).into()
+}
+
+fn config() -> Config {
+    Config {
+        name: "server".to_string(),
+        port: 8080,
+        ratio: 0.5,
+        tags: vec!["a".to_string(), "b".to_string()],
+        limits: vec![("max".to_string(), i64::MAX)].into_iter().collect(),
+        parent: Some(Box::new(Config {
+            name: "parent".to_string(),
+            port: 0,
+            ratio: -1.0,
+            tags: vec![],
+            limits: BTreeMap::new(),
+            parent: None,
+            mode: Mode::Off,
+            key: vec![],
+        })),
+        mode: Mode::Range { min: -1, max: 1 },
+        key: vec![0, 1, 255],
+    }
+}
+
+#[test]
+pub fn serialize_to_java_objects() {
+    let env = attach_current_thread();
+    let obj = unwrap(&env, to_java(&env, &config()));
+    assert!(unwrap(&env, env.is_instance_of(obj, "java/util/HashMap")));
+
+    let map = unwrap(&env, env.get_map(obj));
+    let get = |key: &str| {
+        let key = unwrap(&env, env.new_string(key));
+        unwrap(&env, map.get(key.into())).unwrap_or_else(JObject::null)
+    };
+
+    let port = get("port");
+    assert!(unwrap(&env, env.is_instance_of(port, "java/lang/Integer")));
+    assert_eq!(unwrap(&env, env.unbox_value::<i32>(port)), 8080);
+    assert_eq!(to_string(&env, get("tags")), "[a, b]");
+    assert_eq!(to_string(&env, get("limits")), "{max=9223372036854775807}");
+    assert_eq!(to_string(&env, get("mode")), "{Range={min=-1, max=1}}");
+    assert!(unwrap(&env, env.is_instance_of(get("key"), "[B")));
+
+    let parent = unwrap(&env, env.get_map(get("parent")));
+    let key = unwrap(&env, env.new_string("parent"));
+    assert!(unwrap(&env, parent.get(key.into())).unwrap().is_null());
+
+    let off = unwrap(&env, to_java(&env, &Mode::Off));
+    assert_eq!(to_string(&env, off), "Off");
+    let pair = unwrap(&env, to_java(&env, &Mode::Pair('x', true)));
+    assert_eq!(to_string(&env, pair), "{Pair=[x, true]}");
+}
+
+#[test]
+pub fn serde_roundtrip() {
+    let env = attach_current_thread();
+
+    let obj = unwrap(&env, to_java(&env, &config()));
+    assert_eq!(unwrap(&env, from_java::<Config>(&env, obj)), config());
+
+    for mode in [
+        Mode::Off,
+        Mode::Fixed(u32::MAX),
+        Mode::Pair('\u{1f600}', false),
+    ] {
+        let obj = unwrap(&env, to_java(&env, &mode));
+        assert_eq!(unwrap(&env, from_java::<Mode>(&env, obj)), mode);
+    }
+
+    let values: HashMap<u64, Option<(i8, String)>> = vec![(1, Some((-1, "one".into()))), (2, None)]
+        .into_iter()
+        .collect();
+    let obj = unwrap(&env, to_java(&env, &values));
+    assert_eq!(unwrap(&env, from_java::<HashMap<_, _>>(&env, obj)), values);
+}
+
+#[test]
+pub fn deserialize_java_objects() {
+    let env = attach_current_thread();
+
+    let ints = unwrap(&env, env.array_from_slice(&[1, -2, 3]));
+    let ints: Vec<i64> = unwrap(&env, from_java(&env, ints.into()));
+    assert_eq!(ints, [1, -2, 3]);
+
+    let bytes = unwrap(&env, env.byte_array_from_slice(&[1, 2]));
+    let bytes: Vec<u8> = unwrap(&env, from_java(&env, bytes.into()));
+    assert_eq!(bytes, [1, 2]);
+
+    let strings = unwrap(&env, env.new_string_array(&["x", "y"]));
+    let strings: (String, String) = unwrap(&env, from_java(&env, strings.into()));
+    assert_eq!(strings, ("x".to_string(), "y".to_string()));
+
+    let set = unwrap(&env, env.new_object("java/util/HashSet", "()V", &[]));
+    unwrap(
+        &env,
+        env.call_method(
+            set,
+            "add",
+            "(Ljava/lang/Object;)Z",
+            &[unwrap(&env, env.box_value(7i16)).into()],
+        ),
+    );
+    let set: Vec<u8> = unwrap(&env, from_java(&env, set));
+    assert_eq!(set, [7]);
+
+    let none: Option<String> = unwrap(&env, from_java(&env, JObject::null()));
+    assert_eq!(none, None);
+}
+
+#[test]
+pub fn serde_errors() {
+    let env = attach_current_thread();
+
+    match to_java(&env, &u64::MAX) {
+        Err(Error::Conversion(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let large = unwrap(&env, env.box_value(300));
+    match from_java::<u8>(&env, large) {
+        Err(Error::Conversion(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let thread = unwrap(
+        &env,
+        env.call_static_method(
+            "java/lang/Thread",
+            "currentThread",
+            "()Ljava/lang/Thread;",
+            &[],
+        ),
+    );
+    match from_java::<String>(&env, unwrap(&env, thread.l())) {
+        Err(Error::Conversion(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(!unwrap(&env, env.exception_check()));
+}